@@ -1,3 +1,5 @@
+use std::{error::Error, fmt};
+
 use crate::diagnostic_code::{DiagnosticCode, Severity};
 
 /// Represents a source code location
@@ -7,6 +9,8 @@ pub struct Span {
   pub line: usize,
   pub column: usize,
   pub length: usize,
+  /// Byte offset of the first char in the source, lines and columns count chars instead.
+  pub byte_offset: usize,
 }
 
 /// Label for underlining specific parts of code
@@ -64,6 +68,12 @@ impl Diagnostic {
     }
   }
 
+  /// Function that creates a diagnostic carrying the code's default message, for call sites
+  /// that have nothing more specific to say.
+  pub fn from_code(code: DiagnosticCode) -> Self {
+    Self::new(code, code.to_string())
+  }
+
   pub fn with_label(mut self, label: Label) -> Self {
     self.labels.push(label);
     self
@@ -79,3 +89,22 @@ impl Diagnostic {
     self
   }
 }
+
+/// Shows the header line the formatter prints, `error: [E0001]: unterminated string`.
+impl fmt::Display for Diagnostic {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}: [{}]: {}",
+      self.severity,
+      self.code.code(),
+      self.message
+    )
+  }
+}
+
+impl Error for Diagnostic {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    Some(&self.code)
+  }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 /// Severity level of a diagnostic
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Severity {
@@ -7,11 +9,29 @@ pub enum Severity {
   Help,
 }
 
+impl fmt::Display for Severity {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let text = match self {
+      Severity::Error => "error",
+      Severity::Warning => "warning",
+      Severity::Note => "note",
+      Severity::Help => "help",
+    };
+    write!(f, "{}", text)
+  }
+}
+
 /// Unique identifier for each type of diagnostic
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DiagnosticCode {
   // Errors
   UnterminatedString,
+  /// `/*` comment that is still open at the end of the source
+  UnterminatedComment,
+  /// Unknown `\` escape in a string, holding the char that followed the backslash
+  InvalidEscapeSequence(char),
+  /// Literal that is well formed but names no value, like the surrogate escape `\u{D800}`
+  InvalidLiteral,
   InvalidCharacter,
   InvalidSuperclass,
   InvalidNumber,
@@ -44,12 +64,15 @@ pub enum DiagnosticCode {
   InvalidUnaryOperator,
   TypeError,
   DivisionByZero,
+  RuntimeError,
   ExpectedToken,
 
   // Warning
   UnusedVariable,
   UnreachableCode,
   ImplicitConversion,
+  /// `/*` inside a block comment, comments don't nest so the first `*/` still closes it
+  NestedComment,
 }
 
 impl DiagnosticCode {
@@ -61,6 +84,9 @@ impl DiagnosticCode {
       Self::InvalidNumber => "E0003".to_string(),
       Self::UnexpectedEof => "E0004".to_string(),
       Self::VariableAlreadyDeclared => "E0005".to_string(),
+      Self::InvalidEscapeSequence(_) => "E0006".to_string(),
+      Self::InvalidLiteral => "E0007".to_string(),
+      Self::UnterminatedComment => "E0008".to_string(),
       Self::UnexpectedToken => "E0100".to_string(),
       Self::ExpectedExpression => "E0101".to_string(),
       Self::MissingClosingBrace => "E0102".to_string(),
@@ -82,6 +108,7 @@ impl DiagnosticCode {
       Self::EccededNumberOfArguments => "E0206".to_string(),
       Self::TypeError => "E0207".to_string(),
       Self::DivisionByZero => "E0208".to_string(),
+      Self::RuntimeError => "E0209".to_string(),
       Self::CannotInferType => "E0300".to_string(),
       Self::RecursiveType => "E0301".to_string(),
       Self::FileNotFound => "E0400".to_string(),
@@ -95,13 +122,76 @@ impl DiagnosticCode {
       Self::UnusedVariable => "W0001".to_string(),
       Self::UnreachableCode => "W0002".to_string(),
       Self::ImplicitConversion => "W0003".to_string(),
+      Self::NestedComment => "W0004".to_string(),
     }
   }
 
   pub fn severity(&self) -> Severity {
     match self {
-      Self::UnusedVariable | Self::UnreachableCode | Self::ImplicitConversion => Severity::Warning,
+      Self::UnusedVariable
+      | Self::UnreachableCode
+      | Self::ImplicitConversion
+      | Self::NestedComment => Severity::Warning,
       _ => Severity::Error,
     }
   }
 }
+
+impl fmt::Display for DiagnosticCode {
+  /// Function that writes the default, human-readable message of the code.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let message = match self {
+      // Errors
+      Self::UnterminatedString => "unterminated string",
+      Self::UnterminatedComment => "unterminated comment",
+      Self::InvalidEscapeSequence(escape) => {
+        return write!(f, "invalid escape sequence: '\\{}'", escape)
+      },
+      Self::InvalidLiteral => "invalid literal",
+      Self::InvalidCharacter => "invalid character",
+      Self::InvalidSuperclass => "invalid superclass",
+      Self::InvalidNumber => "invalid number",
+      Self::UnexpectedEof => "unexpected end of file",
+      Self::UnexpectedToken => "unexpected token",
+      Self::ExpectedExpression => "expected expression",
+      Self::InvalidThis => "invalid use of this",
+      Self::MissingClosingBrace => "missing closing brace",
+      Self::MissingClosingParen => "missing closing parenthesis",
+      Self::MissingSemicolon => "missing semicolon",
+      Self::InvalidAssignmentTarget => "invalid assignment target",
+      Self::ExpectedIdentifier => "expected identifier",
+      Self::VariableAlreadyDeclared => "variable already declared",
+      Self::ContinueOutsideLoop => "continue outside of loop",
+      Self::BreakOutsideLoop => "break outside of loop",
+      Self::UndeclaredVariable => "undeclared variable",
+      Self::TypeMismatch => "type mismatch",
+      Self::DuplicateDeclaration => "duplicate declaration",
+      Self::InvalidAssignment => "invalid assignment",
+      Self::InvalidOperator => "invalid operator",
+      Self::ReturnNotInFunction => "return outside of function",
+      Self::InvalidFunctionCall => "invalid function call",
+      Self::WrongNumberOfArguments => "wrong number of arguments",
+      Self::EccededNumberOfArguments => "too many arguments",
+      Self::CannotInferType => "cannot infer type",
+      Self::RecursiveType => "recursive type",
+      Self::FileNotFound => "file not found",
+      Self::InvalidArguments => "invalid arguments",
+      Self::IoError => "io error",
+      Self::InvalidUnaryOperator => "invalid unary operator",
+      Self::TypeError => "type error",
+      Self::DivisionByZero => "division by zero",
+      Self::RuntimeError => "runtime error",
+      Self::ExpectedToken => "expected token",
+
+      // Warnings
+      Self::UnusedVariable => "unused variable",
+      Self::UnreachableCode => "unreachable code",
+      Self::ImplicitConversion => "implicit conversion",
+      Self::NestedComment => "nested comment",
+    };
+
+    write!(f, "{}", message)
+  }
+}
+
+impl std::error::Error for DiagnosticCode {}
@@ -20,13 +20,8 @@ impl<'a> DiagnosticFormatter<'a> {
     }
   }
 
-  fn severity_text(&self) -> &'static str {
-    match self.diagnostic.severity {
-      Severity::Error => "error",
-      Severity::Warning => "warning",
-      Severity::Note => "note",
-      Severity::Help => "help",
-    }
+  fn severity_text(&self) -> String {
+    self.diagnostic.severity.to_string()
   }
 
   fn underline_char(&self, style: LabelStyle) -> char {
@@ -75,7 +70,7 @@ impl<'a> DiagnosticFormatter<'a> {
       },
     };
     output.push_str(&header);
-    output.push_str("\n");
+    output.push('\n');
 
     // Get the primary label (first one, usually)
     if let Some(primary_label) = self.diagnostic.labels.first() {
@@ -108,7 +103,7 @@ impl<'a> DiagnosticFormatter<'a> {
           // Only show labels that are on the same line
           if label.span.line == line_num {
             let underline_char = self.underline_char(label.style);
-            let start_col = label.span.column;
+            let start_col = label.span.column.saturating_sub(1);
             let length = label.span.length;
 
             let padding = " ".repeat(start_col);
@@ -7,24 +7,54 @@ use colored::*;
 use crate::{diagnostic::Diagnostic, diagnostic_code::Severity, formatter::DiagnosticFormatter};
 
 /// Collector for all diagnostics during compilation
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct DiagnosticEngine {
   diagnostics: Vec<Diagnostic>,
   error_count: usize,
   warning_count: usize,
+  /// Name of the compiled source, every emitted span is reported against it
+  source_name: String,
+}
+
+impl Default for DiagnosticEngine {
+  fn default() -> Self {
+    Self {
+      diagnostics: vec![],
+      error_count: 0,
+      warning_count: 0,
+      source_name: "<script>".to_string(),
+    }
+  }
 }
 
 impl DiagnosticEngine {
   pub fn new() -> Self {
     Self::default()
   }
+
+  /// Engine for diagnostics reported against the file at `path`
+  pub fn for_file(path: &str) -> Self {
+    Self {
+      source_name: path.to_string(),
+      ..Self::default()
+    }
+  }
+
+  pub fn source_name(&self) -> &str {
+    &self.source_name
+  }
   pub fn clear(&mut self) {
     self.diagnostics = vec![];
     self.error_count = 0;
     self.warning_count = 0;
   }
 
-  pub fn emit(&mut self, diagnostic: Diagnostic) {
+  pub fn emit(&mut self, mut diagnostic: Diagnostic) {
+    // The stages that build spans don't know which file they are in.
+    for label in &mut diagnostic.labels {
+      label.span.file = self.source_name.clone();
+    }
+
     match diagnostic.severity {
       Severity::Error => self.error_count += 1,
       Severity::Warning => self.warning_count += 1,
@@ -33,6 +63,11 @@ impl DiagnosticEngine {
     self.diagnostics.push(diagnostic);
   }
 
+  /// Whether the next compilation stage should run, i.e. no error was reported so far
+  pub fn proceed(&self) -> bool {
+    !self.has_errors()
+  }
+
   pub fn has_errors(&self) -> bool {
     self.error_count > 0
   }
@@ -45,6 +80,11 @@ impl DiagnosticEngine {
     self.warning_count
   }
 
+  /// Consume the engine and hand back the emitted diagnostics
+  pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+    self.diagnostics
+  }
+
   /// Print all diagnostics with colors to stdout
   pub fn print_all(&self, source_code: &str) {
     for diagnostic in &self.diagnostics {
@@ -62,7 +102,7 @@ impl DiagnosticEngine {
     for diagnostic in &self.diagnostics {
       let formatter = DiagnosticFormatter::new(diagnostic, source_code);
       output.push_str(&formatter.format_plain());
-      output.push_str("\n");
+      output.push('\n');
     }
 
     output.push_str(&self.format_summary_plain());
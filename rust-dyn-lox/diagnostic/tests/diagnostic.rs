@@ -1,10 +1,8 @@
 #[cfg(test)]
 mod tests {
-
-  use super::*;
   use diagnostic::{
     diagnostic::{Diagnostic, Label, Span},
-    diagnostic_code::DiagnosticCode,
+    diagnostic_code::{DiagnosticCode, Severity},
     DiagnosticEngine,
   };
 
@@ -24,6 +22,7 @@ mod tests {
         line: 0,
         column: 18,
         length: 7,
+        byte_offset: 8,
       },
       Some("newline not allowed in string".to_string()),
     ))
@@ -32,4 +31,67 @@ mod tests {
     engine.emit(error);
     println!("{}", engine.format_all_plain(source));
   }
+
+  #[test]
+  fn test_spans_report_the_source_name() {
+    let source = "var = 1;";
+    let span = Span {
+      file: "input".to_string(),
+      line: 1,
+      column: 4,
+      length: 1,
+      byte_offset: 4,
+    };
+
+    let mut engine = DiagnosticEngine::for_file("myfile.duck");
+    engine.emit(
+      Diagnostic::new(
+        DiagnosticCode::ExpectedIdentifier,
+        "Expected identifier after 'var'".to_string(),
+      )
+      .with_label(Label::primary(span.clone(), None)),
+    );
+    assert!(engine.format_all_plain(source).contains("myfile.duck:1:4"));
+
+    let mut engine = DiagnosticEngine::new();
+    engine.emit(
+      Diagnostic::new(DiagnosticCode::ExpectedIdentifier, "".to_string())
+        .with_label(Label::primary(span, None)),
+    );
+    assert_eq!(engine.get_diagnostics()[0].labels[0].span.file, "<script>");
+  }
+
+  #[test]
+  fn test_code_display_is_the_default_message() {
+    assert_eq!(
+      DiagnosticCode::UnexpectedToken.to_string(),
+      "unexpected token"
+    );
+    assert_eq!(
+      DiagnosticCode::DivisionByZero.to_string(),
+      "division by zero"
+    );
+
+    let diagnostic = Diagnostic::from_code(DiagnosticCode::UnterminatedString);
+    assert_eq!(diagnostic.message, "unterminated string");
+    assert_eq!(diagnostic.severity, Severity::Error);
+  }
+
+  #[test]
+  fn test_diagnostics_are_std_errors() {
+    fn scan() -> Result<(), Box<dyn std::error::Error>> {
+      Err(Diagnostic::new(
+        DiagnosticCode::UnterminatedString,
+        "unterminated string".to_string(),
+      ))?
+    }
+
+    let error = scan().unwrap_err();
+    assert_eq!(error.to_string(), "error: [E0001]: unterminated string");
+    assert_eq!(error.source().unwrap().to_string(), "unterminated string");
+
+    let warning = Diagnostic::from_code(DiagnosticCode::UnusedVariable);
+    assert_eq!(warning.to_string(), "warning: [W0001]: unused variable");
+    assert_eq!(Severity::Note.to_string(), "note");
+  }
 }
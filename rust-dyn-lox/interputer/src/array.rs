@@ -0,0 +1,293 @@
+use std::{collections::HashSet, rc::Rc};
+
+use diagnostic::DiagnosticEngine;
+use scanner::token::Token;
+
+use crate::{
+  error::type_error,
+  freezable::Freezable,
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+  map::{key_of, MapEntries, MapKey},
+};
+
+/// Elements of an array value, shared by every variable holding the array
+pub type LoxArray = Rc<Freezable<Vec<LoxValue>>>;
+
+/// Native method of an array, bound to the array it was read from
+pub struct ArrayMethod {
+  array: LoxArray,
+  name: &'static str,
+  arity: usize,
+}
+
+impl ArrayMethod {
+  /// Function that looks up the method `name` on `array`, `None` when arrays have no such method.
+  pub fn bind(array: &LoxArray, name: &str) -> Option<LoxValue> {
+    let (name, arity) = match name {
+      "zip" => ("zip", 1),
+      "unzip" => ("unzip", 0),
+      "flat_map" => ("flat_map", 1),
+      "flatten" => ("flatten", 1),
+      "unique" => ("unique", 0),
+      "group_by" => ("group_by", 1),
+      "chunk" => ("chunk", 1),
+      "push" => ("push", 1),
+      "pop" => ("pop", 0),
+      _ => return None,
+    };
+
+    Some(LoxValue::NativeFunction(Rc::new(ArrayMethod {
+      array: array.clone(),
+      name,
+      arity,
+    })))
+  }
+
+  /// Function that pairs the elements of both arrays, the shorter array sets the length.
+  fn zip(
+    &self,
+    other: &LoxValue,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let LoxValue::Array(other) = other else {
+      return Err(type_error(
+        engine,
+        "zip expects an array argument".to_string(),
+      ));
+    };
+
+    let pairs = self
+      .array
+      .borrow()
+      .iter()
+      .zip(other.borrow().iter())
+      .map(|(first, second)| LoxValue::array(vec![first.clone(), second.clone()]))
+      .collect();
+
+    Ok(LoxValue::array(pairs))
+  }
+
+  /// Function that splits an array of pairs into the array of firsts and the array of seconds.
+  fn unzip(&self, engine: &mut DiagnosticEngine) -> Result<LoxValue, InterpreterError> {
+    let mut firsts = vec![];
+    let mut seconds = vec![];
+
+    for (index, element) in self.array.borrow().iter().enumerate() {
+      let pair = match element {
+        LoxValue::Array(pair) if pair.borrow().len() == 2 => pair.borrow(),
+        _ => {
+          return Err(type_error(
+            engine,
+            format!(
+              "unzip expects an array of pairs, element {} is {}",
+              index,
+              element.to_lox_string()
+            ),
+          ))
+        },
+      };
+
+      firsts.push(pair[0].clone());
+      seconds.push(pair[1].clone());
+    }
+
+    Ok(LoxValue::array(vec![
+      LoxValue::array(firsts),
+      LoxValue::array(seconds),
+    ]))
+  }
+
+  /// Function that calls `function` on every element and concatenates the arrays it returns.
+  fn flat_map(
+    &self,
+    interpreter: &mut Interpreter,
+    function: &LoxValue,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    // The callback may read the array, so don't keep it borrowed while calling.
+    let elements = self.array.borrow().clone();
+    let mut mapped = vec![];
+
+    for element in elements {
+      match interpreter.call_value(function, vec![element], engine)? {
+        LoxValue::Array(result) => mapped.extend(result.borrow().iter().cloned()),
+        result => {
+          return Err(type_error(
+            engine,
+            format!(
+              "flat_map expects the function to return an array, got {}",
+              result.to_lox_string()
+            ),
+          ))
+        },
+      }
+    }
+
+    Ok(LoxValue::array(mapped))
+  }
+
+  /// Function that removes up to `depth` levels of nested arrays.
+  fn flatten(
+    &self,
+    depth: &LoxValue,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let depth = match depth {
+      LoxValue::Number(depth) if depth.is_finite() && *depth >= 0.0 && depth.fract() == 0.0 => {
+        *depth as usize
+      },
+      _ => {
+        return Err(type_error(
+          engine,
+          format!(
+            "flatten expects a non-negative integer depth, got {}",
+            depth.to_lox_string()
+          ),
+        ))
+      },
+    };
+
+    let mut flat = vec![];
+    let mut ancestors = vec![Rc::as_ptr(&self.array) as *const ()];
+    flatten_into(&mut flat, &self.array.borrow(), depth, &mut ancestors)?;
+    Ok(LoxValue::array(flat))
+  }
+
+  /// Function that returns the elements without duplicates, keeping the first of each.
+  fn unique(&self) -> LoxValue {
+    let mut seen = HashSet::new();
+    // Values that can't be map keys are compared one by one, they never equal a hashable value.
+    let mut unhashable: Vec<LoxValue> = vec![];
+    let mut unique = vec![];
+
+    for element in self.array.borrow().iter() {
+      let is_new = match MapKey::from_value(element) {
+        Some(key) => seen.insert(key),
+        None if unhashable.contains(element) => false,
+        None => {
+          unhashable.push(element.clone());
+          true
+        },
+      };
+
+      if is_new {
+        unique.push(element.clone());
+      }
+    }
+
+    LoxValue::array(unique)
+  }
+
+  /// Function that collects the elements into a map from the key `function` returns for each of
+  /// them to the array of elements sharing that key.
+  fn group_by(
+    &self,
+    interpreter: &mut Interpreter,
+    function: &LoxValue,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    // The callback may read the array, so don't keep it borrowed while calling.
+    let elements = self.array.borrow().clone();
+    let mut groups = MapEntries::new();
+
+    for element in elements {
+      let key = interpreter.call_value(function, vec![element.clone()], engine)?;
+      let (_, group) =
+        groups.get_or_insert_with(key_of(&key, engine)?, || (key, LoxValue::array(vec![])));
+
+      if let LoxValue::Array(group) = group {
+        group.borrow_mut().push(element);
+      }
+    }
+
+    Ok(LoxValue::map(groups))
+  }
+
+  /// Function that splits the elements into arrays of `size` elements, the last one holds what
+  /// is left.
+  fn chunk(&self, size: &LoxValue) -> Result<LoxValue, InterpreterError> {
+    let size = match size {
+      LoxValue::Number(size) if *size >= 1.0 && size.fract() == 0.0 => *size as usize,
+      _ => {
+        return Err(InterpreterError::RuntimeError(format!(
+          "Chunk size must be a positive integer, got {}",
+          size.to_lox_string()
+        )))
+      },
+    };
+
+    let chunks = self
+      .array
+      .borrow()
+      .chunks(size)
+      .map(|chunk| LoxValue::array(chunk.to_vec()))
+      .collect();
+
+    Ok(LoxValue::array(chunks))
+  }
+}
+
+/// Function that pushes `elements` into `flat`, unwrapping nested arrays `depth` levels deep.
+/// `ancestors` holds the arrays being unwrapped, so an array holding itself is reported instead of
+/// unwrapping it until the depth runs out.
+fn flatten_into(
+  flat: &mut Vec<LoxValue>,
+  elements: &[LoxValue],
+  depth: usize,
+  ancestors: &mut Vec<*const ()>,
+) -> Result<(), InterpreterError> {
+  for element in elements {
+    match element {
+      LoxValue::Array(nested) if depth > 0 => {
+        let pointer = Rc::as_ptr(nested) as *const ();
+        if ancestors.contains(&pointer) {
+          return Err(InterpreterError::RuntimeError(
+            "Cannot flatten circular structure.".to_string(),
+          ));
+        }
+
+        ancestors.push(pointer);
+        flatten_into(flat, &nested.borrow(), depth - 1, ancestors)?;
+        ancestors.pop();
+      },
+      _ => flat.push(element.clone()),
+    }
+  }
+  Ok(())
+}
+
+impl LoxCallable for ArrayMethod {
+  fn arity(&self) -> usize {
+    self.arity
+  }
+
+  fn call(
+    &self,
+    interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let arguments = arguments
+      .into_iter()
+      .map(|(value, _)| value)
+      .collect::<Vec<_>>();
+
+    match self.name {
+      "zip" => self.zip(&arguments[0], engine),
+      "unzip" => self.unzip(engine),
+      "flat_map" => self.flat_map(interpreter, &arguments[0], engine),
+      "flatten" => self.flatten(&arguments[0], engine),
+      "unique" => Ok(self.unique()),
+      "group_by" => self.group_by(interpreter, &arguments[0], engine),
+      "chunk" => self.chunk(&arguments[0]),
+      "push" => {
+        self.array.mutate()?.push(arguments[0].clone());
+        Ok(LoxValue::Nil)
+      },
+      "pop" => Ok(self.array.mutate()?.pop().unwrap_or(LoxValue::Nil)),
+      _ => unreachable!("unknown array method '{}'", self.name),
+    }
+  }
+}
@@ -1,21 +1,97 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use crate::{
   function::{normal::LoxFunction, LoxCallable},
   lox_value::{InterpreterError, LoxValue},
 };
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct LoxClass {
   pub name: String,
   pub superclass: LoxValue,
-  pub methods: HashMap<String, Arc<LoxFunction>>,
-  pub static_methods: HashMap<String, Arc<LoxFunction>>,
+  pub methods: HashMap<String, Rc<LoxFunction>>,
+  pub static_methods: HashMap<String, Rc<LoxFunction>>,
+  /// Methods defined by the host, they receive `this` as their first argument
+  pub native_methods: HashMap<String, Rc<dyn LoxCallable>>,
+  /// Fields every new instance starts with, before `init` runs
+  pub field_defaults: Vec<(String, LoxValue)>,
+}
+
+// Implement Debug manually, native methods aren't Debug
+impl std::fmt::Debug for LoxClass {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("LoxClass")
+      .field("name", &self.name)
+      .field("superclass", &self.superclass)
+      .field("methods", &self.methods.keys().collect::<Vec<_>>())
+      .field(
+        "static_methods",
+        &self.static_methods.keys().collect::<Vec<_>>(),
+      )
+      .field(
+        "native_methods",
+        &self.native_methods.keys().collect::<Vec<_>>(),
+      )
+      .field("field_defaults", &self.field_defaults)
+      .finish()
+  }
+}
+
+/// Native method bound to the instance it was read from
+pub struct BoundNativeMethod {
+  pub this: Rc<RefCell<LoxClassInstance>>,
+  pub method: Rc<dyn LoxCallable>,
+}
+
+impl LoxCallable for BoundNativeMethod {
+  fn arity(&self) -> usize {
+    self.method.arity()
+  }
+
+  fn call(
+    &self,
+    interpreter: &mut crate::interpreter::Interpreter,
+    mut arguments: Vec<(LoxValue, Option<scanner::token::Token>)>,
+    engine: &mut diagnostic::DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    arguments.insert(0, (LoxValue::Instance(self.this.clone()), None));
+    self.method.call(interpreter, arguments, engine)
+  }
+}
+
+/// Class provided by the interpreter, it only holds static methods implemented natively
+#[derive(Debug)]
+pub struct NativeClass {
+  pub name: &'static str,
+  pub static_methods: HashMap<&'static str, LoxValue>,
 }
 
 pub struct LoxClassInstance {
-  pub class: Arc<LoxClass>,
+  pub class: Rc<LoxClass>,
   pub fields: HashMap<String, crate::lox_value::LoxValue>,
+  /// Instance whose fields are read when this one has no field of the requested name
+  pub prototype: Option<Rc<RefCell<LoxClassInstance>>>,
+}
+
+impl LoxClassInstance {
+  /// Function that returns the field `name` of the instance or, failing that, of the closest
+  /// instance up its prototype chain.
+  pub fn get_field(&self, name: &str) -> Option<LoxValue> {
+    if let Some(field) = self.fields.get(name) {
+      return Some(field.clone());
+    }
+
+    let mut prototype = self.prototype.clone();
+    while let Some(instance) = prototype {
+      let instance = instance.borrow();
+      if let Some(field) = instance.fields.get(name) {
+        return Some(field.clone());
+      }
+      prototype = instance.prototype.clone();
+    }
+
+    None
+  }
 }
 
 // Implement Debug manually to avoid infinite recursion
@@ -32,6 +108,8 @@ impl LoxCallable for LoxClass {
   fn arity(&self) -> usize {
     if let Some(initializer) = self.methods.get("init") {
       initializer.arity()
+    } else if let Some(initializer) = self.native_methods.get("init") {
+      initializer.arity()
     } else {
       0
     }
@@ -45,8 +123,9 @@ impl LoxCallable for LoxClass {
   ) -> Result<crate::lox_value::LoxValue, crate::lox_value::InterpreterError> {
     // STEP 1: Create the instance
     let instance = Rc::new(RefCell::new(LoxClassInstance {
-      class: Arc::new(self.clone()),
-      fields: HashMap::new(),
+      class: Rc::new(self.clone()),
+      fields: self.field_defaults.iter().cloned().collect(),
+      prototype: None,
     }));
 
     // STEP 2: Look for init() method
@@ -56,16 +135,38 @@ impl LoxCallable for LoxClass {
 
       // Check arity (already checked in eval_call, but double-check here)
       if arguments.len() != bound_init.arity() {
-        return Err(InterpreterError::RuntimeError);
+        return Err(InterpreterError::RuntimeError(format!(
+          "Expected {} arguments but got {}",
+          bound_init.arity(),
+          arguments.len()
+        )));
       }
 
       // Call init() with arguments
       // Pass the interpreter, not self!
+      bound_init.call(interpreter, arguments, engine)?;
+    } else if let Some(initializer) = self.find_native_method("init") {
+      let bound_init = BoundNativeMethod {
+        this: instance.clone(),
+        method: initializer,
+      };
+
+      if arguments.len() != bound_init.arity() {
+        return Err(InterpreterError::RuntimeError(format!(
+          "Expected {} arguments but got {}",
+          bound_init.arity(),
+          arguments.len()
+        )));
+      }
+
       bound_init.call(interpreter, arguments, engine)?;
     } else {
       // No init() - must have 0 arguments
       if !arguments.is_empty() {
-        return Err(InterpreterError::RuntimeError);
+        return Err(InterpreterError::RuntimeError(format!(
+          "Expected 0 arguments but got {}",
+          arguments.len()
+        )));
       }
     }
 
@@ -75,18 +176,31 @@ impl LoxCallable for LoxClass {
 }
 
 impl LoxClass {
-  pub fn find_method(&self, name: &str) -> Option<&Arc<LoxFunction>> {
+  pub fn find_method(&self, name: &str) -> Option<&Rc<LoxFunction>> {
     if let Some(method) = self.methods.get(name) {
-      return Some(&method);
+      return Some(method);
     }
 
     if let LoxValue::Class(superclass_arc) = &self.superclass {
       // Recursively call find_method on the superclass's LoxClass
       // Note: We need to check if the superclass is actually a class before calling find_method
-      let superclass_loxclass: &LoxClass = &superclass_arc;
+      let superclass_loxclass: &LoxClass = superclass_arc;
       return superclass_loxclass.find_method(name);
     }
 
     None
   }
+
+  /// Function that looks up the host defined method `name` on the class and its superclasses.
+  pub fn find_native_method(&self, name: &str) -> Option<Rc<dyn LoxCallable>> {
+    if let Some(method) = self.native_methods.get(name) {
+      return Some(method.clone());
+    }
+
+    if let LoxValue::Class(superclass) = &self.superclass {
+      return superclass.find_native_method(name);
+    }
+
+    None
+  }
 }
@@ -7,6 +7,12 @@ pub struct Env {
   pub enclosing: Option<Rc<RefCell<Env>>>,
 }
 
+impl Default for Env {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl Env {
   pub fn new() -> Self {
     Self {
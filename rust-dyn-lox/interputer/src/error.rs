@@ -0,0 +1,9 @@
+use diagnostic::{diagnostic::Diagnostic, diagnostic_code::DiagnosticCode, DiagnosticEngine};
+
+use crate::lox_value::InterpreterError;
+
+/// Function that reports a value of the wrong type passed to a native method.
+pub fn type_error(engine: &mut DiagnosticEngine, message: String) -> InterpreterError {
+  engine.emit(Diagnostic::new(DiagnosticCode::TypeError, message.clone()));
+  InterpreterError::RuntimeError(message)
+}
@@ -0,0 +1,48 @@
+use std::cell::{Cell, Ref, RefCell, RefMut};
+
+use crate::lox_value::InterpreterError;
+
+/// Contents of an array or a map, once frozen the language methods can no longer mutate them
+#[derive(Debug, Default)]
+pub struct Freezable<T> {
+  value: RefCell<T>,
+  is_frozen: Cell<bool>,
+}
+
+impl<T> Freezable<T> {
+  pub fn new(value: T) -> Self {
+    Self {
+      value: RefCell::new(value),
+      is_frozen: Cell::new(false),
+    }
+  }
+
+  pub fn borrow(&self) -> Ref<'_, T> {
+    self.value.borrow()
+  }
+
+  /// Function that borrows the contents mutably whether frozen or not, it is meant for values
+  /// still being built. Mutations asked for by a program go through `mutate`.
+  pub fn borrow_mut(&self) -> RefMut<'_, T> {
+    self.value.borrow_mut()
+  }
+
+  /// Function that borrows the contents mutably, failing once they are frozen.
+  pub fn mutate(&self) -> Result<RefMut<'_, T>, InterpreterError> {
+    if self.is_frozen() {
+      return Err(InterpreterError::RuntimeError(
+        "Cannot mutate frozen object.".to_string(),
+      ));
+    }
+
+    Ok(self.value.borrow_mut())
+  }
+
+  pub fn freeze(&self) {
+    self.is_frozen.set(true);
+  }
+
+  pub fn is_frozen(&self) -> bool {
+    self.is_frozen.get()
+  }
+}
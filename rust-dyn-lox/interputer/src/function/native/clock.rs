@@ -1,5 +1,5 @@
 use std::{
-  sync::Arc,
+  rc::Rc,
   time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -16,7 +16,7 @@ impl ClockFunction {
   pub fn add(interpreter: &mut Interpreter) {
     interpreter.env.borrow_mut().define(
       "clock".to_string(),
-      LoxValue::NativeFunction(Arc::new(ClockFunction)),
+      LoxValue::NativeFunction(Rc::new(ClockFunction)),
     );
   }
 }
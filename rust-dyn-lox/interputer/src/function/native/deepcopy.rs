@@ -0,0 +1,103 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+  class::LoxClassInstance,
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+};
+
+/// Deepcopy native function, `deepcopy(value)` clones arrays, maps and instance fields all the
+/// way down
+pub struct DeepcopyFunction;
+
+impl DeepcopyFunction {
+  pub fn add(interpreter: &mut Interpreter) {
+    interpreter.env.borrow_mut().define(
+      "deepcopy".to_string(),
+      LoxValue::NativeFunction(Rc::new(DeepcopyFunction)),
+    );
+  }
+
+  /// Function that copies `value` all the way down, the way `deepcopy(value)` does in a program.
+  pub(crate) fn copy(value: &LoxValue) -> Result<LoxValue, InterpreterError> {
+    deepcopy(value, &mut vec![])
+  }
+}
+
+/// Function that copies `value` recursively, `ancestors` holds the containers being copied so a
+/// container holding itself is reported instead of recursing forever. Functions, classes and
+/// instances without fields are shared with the original.
+fn deepcopy(
+  value: &LoxValue,
+  ancestors: &mut Vec<*const ()>,
+) -> Result<LoxValue, InterpreterError> {
+  let pointer = match value {
+    LoxValue::Array(array) => Rc::as_ptr(array) as *const (),
+    LoxValue::Map(map) => Rc::as_ptr(map) as *const (),
+    LoxValue::Instance(instance) if !instance.borrow().fields.is_empty() => {
+      Rc::as_ptr(instance) as *const ()
+    },
+    _ => return Ok(value.clone()),
+  };
+
+  if ancestors.contains(&pointer) {
+    return Err(InterpreterError::RuntimeError(
+      "Circular reference in deepcopy.".to_string(),
+    ));
+  }
+  ancestors.push(pointer);
+
+  let copy = match value {
+    LoxValue::Array(array) => LoxValue::array(
+      array
+        .borrow()
+        .iter()
+        .map(|element| deepcopy(element, ancestors))
+        .collect::<Result<_, _>>()?,
+    ),
+    // Keys are nil, booleans, numbers or strings, so only the values need copying.
+    LoxValue::Map(map) => LoxValue::map(
+      map
+        .borrow()
+        .iter()
+        .map(|(hashed, (key, value))| {
+          Ok((hashed.clone(), (key.clone(), deepcopy(value, ancestors)?)))
+        })
+        .collect::<Result<_, InterpreterError>>()?,
+    ),
+    LoxValue::Instance(instance) => {
+      let instance = instance.borrow();
+      let fields = instance
+        .fields
+        .iter()
+        .map(|(name, value)| Ok((name.clone(), deepcopy(value, ancestors)?)))
+        .collect::<Result<_, InterpreterError>>()?;
+
+      LoxValue::Instance(Rc::new(RefCell::new(LoxClassInstance {
+        class: instance.class.clone(),
+        fields,
+        prototype: instance.prototype.clone(),
+      })))
+    },
+    _ => unreachable!(),
+  };
+
+  ancestors.pop();
+  Ok(copy)
+}
+
+impl LoxCallable for DeepcopyFunction {
+  fn arity(&self) -> usize {
+    1
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<scanner::token::Token>)>,
+    _engine: &mut diagnostic::DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    Self::copy(&arguments[0].0)
+  }
+}
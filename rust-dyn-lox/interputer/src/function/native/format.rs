@@ -0,0 +1,69 @@
+use std::rc::Rc;
+
+use crate::{
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+};
+
+/// Format native function, `format(template, ...args)` fills every `{}` of the template
+pub struct FormatFunction;
+
+impl FormatFunction {
+  pub fn add(interpreter: &mut Interpreter) {
+    interpreter.env.borrow_mut().define(
+      "format".to_string(),
+      LoxValue::NativeFunction(Rc::new(FormatFunction)),
+    );
+  }
+}
+
+/// Function that substitutes each `{}` of `template` with the next argument, formatted like
+/// `print` does. Arguments left once every placeholder is filled are ignored.
+pub fn format_string(template: &str, arguments: &[LoxValue]) -> Result<String, InterpreterError> {
+  let placeholders = template.matches("{}").count();
+  if placeholders > arguments.len() {
+    return Err(InterpreterError::RuntimeError(format!(
+      "format expected {} arguments for the template but got {}",
+      placeholders,
+      arguments.len()
+    )));
+  }
+
+  let mut output = String::with_capacity(template.len());
+  let mut arguments = arguments.iter();
+  let mut parts = template.split("{}");
+
+  output.push_str(parts.next().unwrap_or_default());
+  for part in parts {
+    // There are at least as many arguments as placeholders.
+    output.push_str(&arguments.next().unwrap().to_lox_string());
+    output.push_str(part);
+  }
+
+  Ok(output)
+}
+
+impl LoxCallable for FormatFunction {
+  fn arity(&self) -> usize {
+    usize::MAX
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut crate::interpreter::Interpreter,
+    arguments: Vec<(crate::lox_value::LoxValue, Option<scanner::token::Token>)>,
+    _engine: &mut diagnostic::DiagnosticEngine,
+  ) -> Result<crate::lox_value::LoxValue, InterpreterError> {
+    let mut values = arguments.into_iter().map(|(value, _)| value);
+
+    let Some(LoxValue::String(template)) = values.next() else {
+      return Err(InterpreterError::RuntimeError(
+        "format expects a template string as its first argument".to_string(),
+      ));
+    };
+
+    let output = format_string(&template, &values.collect::<Vec<_>>())?;
+    Ok(LoxValue::String(output))
+  }
+}
@@ -0,0 +1,76 @@
+use std::rc::Rc;
+
+use crate::{
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+};
+
+/// Freeze native function, `freeze(value)` makes an array or a map immutable and returns it.
+/// Nested arrays and maps stay mutable.
+pub struct FreezeFunction;
+
+/// Is frozen native function, `is_frozen(value)` is `true` for frozen arrays and maps
+pub struct IsFrozenFunction;
+
+impl FreezeFunction {
+  pub fn add(interpreter: &mut Interpreter) {
+    let mut env = interpreter.env.borrow_mut();
+    env.define(
+      "freeze".to_string(),
+      LoxValue::NativeFunction(Rc::new(FreezeFunction)),
+    );
+    env.define(
+      "is_frozen".to_string(),
+      LoxValue::NativeFunction(Rc::new(IsFrozenFunction)),
+    );
+  }
+}
+
+impl LoxCallable for FreezeFunction {
+  fn arity(&self) -> usize {
+    1
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<scanner::token::Token>)>,
+    _engine: &mut diagnostic::DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let value = &arguments[0].0;
+    match value {
+      LoxValue::Array(array) => array.freeze(),
+      LoxValue::Map(map) => map.freeze(),
+      _ => {
+        return Err(InterpreterError::RuntimeError(format!(
+          "Only arrays and maps can be frozen, got {}",
+          value.to_lox_string()
+        )))
+      },
+    }
+
+    Ok(value.clone())
+  }
+}
+
+impl LoxCallable for IsFrozenFunction {
+  fn arity(&self) -> usize {
+    1
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<scanner::token::Token>)>,
+    _engine: &mut diagnostic::DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let is_frozen = match &arguments[0].0 {
+      LoxValue::Array(array) => array.is_frozen(),
+      LoxValue::Map(map) => map.is_frozen(),
+      _ => false,
+    };
+
+    Ok(LoxValue::Bool(is_frozen))
+  }
+}
@@ -0,0 +1,124 @@
+use std::rc::Rc;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hashes are cut to 53 bits so every one of them is exactly representable as a Lox number
+const HASH_MASK: u64 = (1 << 53) - 1;
+
+/// Hash native function, `hash(value)` returns the same number for values that are equal
+pub struct HashFunction;
+
+impl HashFunction {
+  pub fn add(interpreter: &mut Interpreter) {
+    interpreter.env.borrow_mut().define(
+      "hash".to_string(),
+      LoxValue::NativeFunction(Rc::new(HashFunction)),
+    );
+  }
+}
+
+/// Function that feeds `bytes` into the FNV-1a `hash`.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+  bytes.iter().fold(hash, |hash, byte| {
+    (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+  })
+}
+
+/// Function that hashes `value`, values that are equal by `==` hash the same. Functions, classes
+/// and instances have no hash, and neither has an array or map that holds itself.
+pub fn hash_value(value: &LoxValue) -> Result<u64, InterpreterError> {
+  hash_within(value, &mut vec![])
+}
+
+/// Function that hashes `value` like [`hash_value`], `ancestors` holds the arrays and maps being
+/// hashed so a container holding itself is reported instead of recursing forever.
+fn hash_within(value: &LoxValue, ancestors: &mut Vec<*const ()>) -> Result<u64, InterpreterError> {
+  let pointer = match value {
+    LoxValue::Array(array) => Some(Rc::as_ptr(array) as *const ()),
+    LoxValue::Map(map) => Some(Rc::as_ptr(map) as *const ()),
+    _ => None,
+  };
+  if let Some(pointer) = pointer {
+    if ancestors.contains(&pointer) {
+      return Err(InterpreterError::RuntimeError(
+        "Cannot hash circular structure.".to_string(),
+      ));
+    }
+    ancestors.push(pointer);
+  }
+
+  let hash = match value {
+    LoxValue::Nil => fnv1a(FNV_OFFSET_BASIS, b"nil"),
+    LoxValue::Bool(b) => fnv1a(FNV_OFFSET_BASIS, &[*b as u8]),
+    LoxValue::Number(n) => {
+      // `0` and `-0` are equal, and every NaN hashes the same so its hash is stable.
+      let bits = if *n == 0.0 {
+        0.0_f64.to_bits()
+      } else if n.is_nan() {
+        f64::NAN.to_bits()
+      } else {
+        n.to_bits()
+      };
+      fnv1a(FNV_OFFSET_BASIS, &bits.to_le_bytes())
+    },
+    // Strings compare by their NFC form, so that is the form that gets hashed.
+    LoxValue::String(s) => fnv1a(FNV_OFFSET_BASIS, s.nfc().collect::<String>().as_bytes()),
+    LoxValue::Array(array) => {
+      array
+        .borrow()
+        .iter()
+        .try_fold(fnv1a(FNV_OFFSET_BASIS, b"["), |hash, element| {
+          Ok::<_, InterpreterError>(fnv1a(hash, &hash_within(element, ancestors)?.to_le_bytes()))
+        })?
+    },
+    // Equal maps can hold their entries in different orders, so their hashes are combined with
+    // an order-free sum.
+    LoxValue::Map(map) => {
+      map
+        .borrow()
+        .values()
+        .try_fold(fnv1a(FNV_OFFSET_BASIS, b"{"), |hash, (key, value)| {
+          let entry = fnv1a(
+            hash_within(key, ancestors)?,
+            &hash_within(value, ancestors)?.to_le_bytes(),
+          );
+          Ok::<_, InterpreterError>(hash.wrapping_add(entry))
+        })?
+    },
+    _ => {
+      return Err(InterpreterError::RuntimeError(format!(
+        "{} can't be hashed",
+        value.to_lox_string()
+      )))
+    },
+  };
+
+  if pointer.is_some() {
+    ancestors.pop();
+  }
+  Ok(hash & HASH_MASK)
+}
+
+impl LoxCallable for HashFunction {
+  fn arity(&self) -> usize {
+    1
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<scanner::token::Token>)>,
+    _engine: &mut diagnostic::DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    Ok(LoxValue::Number(hash_value(&arguments[0].0)? as f64))
+  }
+}
@@ -1,2 +1,7 @@
 pub mod clock;
+pub mod deepcopy;
+pub mod format;
+pub mod freeze;
+pub mod hash;
 pub mod print;
+pub mod prototype;
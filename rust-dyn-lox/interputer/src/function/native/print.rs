@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::rc::Rc;
 
 use crate::{
   function::LoxCallable,
@@ -13,7 +13,7 @@ impl PrintFunction {
   pub fn add(interpreter: &mut Interpreter) {
     interpreter.env.borrow_mut().define(
       "print".to_string(),
-      LoxValue::NativeFunction(Arc::new(PrintFunction)),
+      LoxValue::NativeFunction(Rc::new(PrintFunction)),
     );
   }
 }
@@ -25,19 +25,17 @@ impl LoxCallable for PrintFunction {
 
   fn call(
     &self,
-    _interpreter: &mut crate::interpreter::Interpreter,
+    interpreter: &mut crate::interpreter::Interpreter,
     arguments: Vec<(crate::lox_value::LoxValue, Option<scanner::token::Token>)>,
     _engine: &mut diagnostic::DiagnosticEngine,
   ) -> Result<crate::lox_value::LoxValue, InterpreterError> {
-    // Map each (LoxValue, _) to string using Display
     let output = arguments
       .iter()
-      .map(|(val, _)| val.to_string())
+      .map(|(val, _)| val.to_lox_string())
       .collect::<Vec<_>>()
       .join(" ");
 
-    // Print to stdout
-    println!("{}", output);
+    writeln!(interpreter.output.borrow_mut(), "{}", output)?;
 
     // Return nil (like Lox `print` does)
     Ok(crate::lox_value::LoxValue::Nil)
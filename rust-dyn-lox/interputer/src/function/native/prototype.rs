@@ -0,0 +1,65 @@
+use std::rc::Rc;
+
+use crate::{
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+};
+
+/// Set prototype native function, `set_prototype(instance, prototype)` makes the instance read
+/// the fields it lacks from `prototype`. A `nil` prototype removes it.
+pub struct SetPrototypeFunction;
+
+impl SetPrototypeFunction {
+  pub fn add(interpreter: &mut Interpreter) {
+    interpreter.env.borrow_mut().define(
+      "set_prototype".to_string(),
+      LoxValue::NativeFunction(Rc::new(SetPrototypeFunction)),
+    );
+  }
+}
+
+impl LoxCallable for SetPrototypeFunction {
+  fn arity(&self) -> usize {
+    2
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<scanner::token::Token>)>,
+    _engine: &mut diagnostic::DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let LoxValue::Instance(instance) = &arguments[0].0 else {
+      return Err(InterpreterError::RuntimeError(format!(
+        "set_prototype expects an instance, got {}",
+        arguments[0].0.to_lox_string()
+      )));
+    };
+
+    let prototype = match &arguments[1].0 {
+      LoxValue::Nil => None,
+      LoxValue::Instance(prototype) => Some(prototype.clone()),
+      value => {
+        return Err(InterpreterError::RuntimeError(format!(
+          "A prototype must be an instance or nil, got {}",
+          value.to_lox_string()
+        )))
+      },
+    };
+
+    // Field lookups walk the chain until it ends, so it must not loop back to the instance.
+    let mut ancestor = prototype.clone();
+    while let Some(current) = ancestor {
+      if Rc::ptr_eq(&current, instance) {
+        return Err(InterpreterError::RuntimeError(
+          "Prototype chains can't be cyclic.".to_string(),
+        ));
+      }
+      ancestor = current.borrow().prototype.clone();
+    }
+
+    instance.borrow_mut().prototype = prototype;
+    Ok(LoxValue::Nil)
+  }
+}
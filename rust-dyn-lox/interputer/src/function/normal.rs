@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc, sync::Arc};
+use std::{cell::RefCell, rc::Rc};
 
 use diagnostic::DiagnosticEngine;
 use parser::stmt::Stmt;
@@ -45,7 +45,7 @@ impl LoxCallable for LoxFunction {
         .define(self.params[i].lexeme.to_string(), arg_val.clone());
     }
 
-    match interpreter.eval_block(Box::new(self.body.clone()), &mut enclosing_env, engine) {
+    match interpreter.eval_block(&self.body, &mut enclosing_env, engine) {
       Ok((v, _)) => {
         if self.is_initializer {
           return Ok(enclosing_env.borrow().get_at(1, "this").unwrap());
@@ -58,22 +58,22 @@ impl LoxCallable for LoxFunction {
           if self.is_initializer {
             return Ok(enclosing_env.borrow().get_at(1, "this").unwrap());
           }
-          return Ok(v);
+          Ok(v)
         },
-        _ => Ok(LoxValue::Nil),
+        error => Err(error),
       },
     }
   }
 }
 
 impl LoxFunction {
-  pub fn bind(&self, instance: Rc<RefCell<LoxClassInstance>>) -> Arc<LoxFunction> {
+  pub fn bind(&self, instance: Rc<RefCell<LoxClassInstance>>) -> Rc<LoxFunction> {
     // Create a new environment with "this" bound to the instance
     let mut environment = Env::new();
     environment.enclosing = Some(self.closure.clone());
     environment.define("this".to_string(), LoxValue::Instance(instance));
 
-    Arc::new(LoxFunction {
+    Rc::new(LoxFunction {
       params: self.params.clone(),
       body: self.body.clone(),
       closure: Rc::new(RefCell::new(environment)),
@@ -0,0 +1,10 @@
+use crate::lox_value::InterpreterError;
+
+/// Source of the modules a program imports, set through
+/// [`crate::interpreter::InterpreterBuilder::with_import_resolver`]. The language has no `import`
+/// statement yet, so a run never asks for a module. [`crate::interpreter::Interpreter::resolve_import`]
+/// is the hook such a statement goes through.
+pub trait ImportResolver {
+  /// Function that returns the source of the module named `path`.
+  fn resolve(&self, path: &str) -> Result<String, InterpreterError>;
+}
@@ -1,94 +1,388 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
+use std::{cell::RefCell, collections::HashMap, fmt, io, io::Write, rc::Rc, time::Instant};
 
 use diagnostic::{
   diagnostic::{Diagnostic, Label, Span},
-  diagnostic_code::DiagnosticCode,
+  diagnostic_code::{DiagnosticCode, Severity},
   DiagnosticEngine,
 };
-use parser::{expr::Expr, stmt::Stmt};
-use scanner::token::{types::Literal, Token};
+use parser::{expr::Expr, stmt::Stmt, Parser};
+use scanner::{
+  token::{stream::TokenStream, types::Literal, Token},
+  Scanner,
+};
+use semantic_analysis::resolver::Resolver;
 
 use crate::{
-  class::{LoxClass, LoxClassInstance},
+  array::ArrayMethod,
+  class::{BoundNativeMethod, LoxClass},
   env::Env,
   function::{
-    native::{clock::ClockFunction, print::PrintFunction},
+    native::{
+      clock::ClockFunction, deepcopy::DeepcopyFunction, format::FormatFunction,
+      freeze::FreezeFunction, hash::HashFunction, print::PrintFunction,
+      prototype::SetPrototypeFunction,
+    },
     normal::LoxFunction,
     LoxCallable,
   },
+  import::ImportResolver,
   lox_value::{InterpreterError, LoxValue},
+  map::{key_of, MapClass, MapEntries, MapMethod},
+  number::{NumberClass, NumberMethod},
+  profiler::Profiler,
+  string::{StringClass, StringMethod},
+  weak::{WeakRefFunction, WeakRefMethod},
 };
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Interpreter {
   pub env: Rc<RefCell<Env>>,
-  pub locals: HashMap<String, usize>,
+  pub locals: HashMap<Token, usize>,
+  /// Where `print` writes to, stdout unless the builder was given another writer
+  pub output: Rc<RefCell<dyn Write>>,
+  /// Where runtime errors are written as they are reported, they only go to the
+  /// `DiagnosticEngine` unless the builder was given a writer
+  pub error_output: Option<Rc<RefCell<dyn Write>>>,
+  /// Where imported modules come from, see [`Interpreter::resolve_import`]
+  pub import_resolver: Option<Rc<dyn ImportResolver>>,
+  /// Limits the program runs under, see [`InterpreterConfig`]
+  pub config: InterpreterConfig,
+  /// Calls made so far, only kept once the builder turned the profiler on
+  pub profiler: Option<Profiler>,
+  /// Calls currently running, checked against [`InterpreterConfig::max_call_depth`]
+  call_depth: usize,
 }
 
-impl Interpreter {
-  pub fn new() -> Self {
-    Self {
+/// Limits an [`Interpreter`] runs programs under, the default sets none.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct InterpreterConfig {
+  /// Calls that may run at once before a call fails with a runtime error, so a runaway
+  /// recursion stops before it overflows the host stack.
+  pub max_call_depth: Option<usize>,
+}
+
+impl fmt::Debug for Interpreter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Interpreter")
+      .field("env", &self.env)
+      .field("locals", &self.locals)
+      .field("config", &self.config)
+      .field("profiler", &self.profiler)
+      .finish_non_exhaustive()
+  }
+}
+
+impl Default for Interpreter {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Builder for an [`Interpreter`] that doesn't use the defaults of [`Interpreter::new`].
+#[derive(Default)]
+pub struct InterpreterBuilder {
+  output: Option<Rc<RefCell<dyn Write>>>,
+  error_output: Option<Rc<RefCell<dyn Write>>>,
+  import_resolver: Option<Rc<dyn ImportResolver>>,
+  globals: Vec<(String, LoxValue)>,
+  config: InterpreterConfig,
+  profiler: bool,
+}
+
+impl InterpreterBuilder {
+  /// Function that sets the writer `print` writes to.
+  pub fn with_output(mut self, output: Rc<RefCell<dyn Write>>) -> Self {
+    self.output = Some(output);
+    self
+  }
+
+  /// Function that sets a writer every runtime error is also written to, one line each.
+  pub fn with_error_output(mut self, error_output: Rc<RefCell<dyn Write>>) -> Self {
+    self.error_output = Some(error_output);
+    self
+  }
+
+  /// Function that sets where imported modules come from.
+  pub fn with_import_resolver(mut self, import_resolver: Rc<dyn ImportResolver>) -> Self {
+    self.import_resolver = Some(import_resolver);
+    self
+  }
+
+  /// Function that sets the limits the program runs under.
+  pub fn with_config(mut self, config: InterpreterConfig) -> Self {
+    self.config = config;
+    self
+  }
+
+  /// Function that turns on the [`Profiler`], which counts and times every call.
+  pub fn with_profiler(mut self) -> Self {
+    self.profiler = true;
+    self
+  }
+
+  /// Function that defines a global the program can read before declaring it.
+  pub fn with_global(mut self, name: &str, value: LoxValue) -> Self {
+    self.globals.push((name.to_string(), value));
+    self
+  }
+
+  /// Function that builds the interpreter with every native already defined, a global given to
+  /// [`Self::with_global`] under the name of a native replaces it.
+  pub fn build(self) -> Interpreter {
+    let mut interpreter = Interpreter {
       env: Rc::new(RefCell::new(Env::new())),
       locals: HashMap::new(),
+      output: self
+        .output
+        .unwrap_or_else(|| Rc::new(RefCell::new(io::stdout()))),
+      error_output: self.error_output,
+      import_resolver: self.import_resolver,
+      config: self.config,
+      profiler: self.profiler.then(Profiler::default),
+      call_depth: 0,
+    };
+
+    PrintFunction::add(&mut interpreter);
+    ClockFunction::add(&mut interpreter);
+    FormatFunction::add(&mut interpreter);
+    HashFunction::add(&mut interpreter);
+    DeepcopyFunction::add(&mut interpreter);
+    FreezeFunction::add(&mut interpreter);
+    WeakRefFunction::add(&mut interpreter);
+    SetPrototypeFunction::add(&mut interpreter);
+    MapClass::add(&mut interpreter);
+    StringClass::add(&mut interpreter);
+    NumberClass::add(&mut interpreter);
+
+    for (name, value) in self.globals {
+      interpreter.env.borrow_mut().define(name, value);
     }
+    interpreter
+  }
+}
+
+impl Interpreter {
+  pub fn new() -> Self {
+    InterpreterBuilder::default().build()
+  }
+
+  /// Function that evaluates the expression `source` in the global scope, it sees every native
+  /// and every global the programs run so far defined. The expression is resolved on its own
+  /// before it runs. The diagnostics of a failed evaluation are returned.
+  pub fn evaluate_expression(&mut self, source: &str) -> Result<LoxValue, Vec<Diagnostic>> {
+    let mut engine = DiagnosticEngine::new();
+
+    let mut scanner = Scanner::new(source);
+    scanner.scan(&mut engine);
+    if !engine.proceed() {
+      return Err(engine.into_diagnostics());
+    }
+
+    // Locals are keyed by token, spans included. The expression's tokens are moved past every
+    // token of the last program, so none of them can pick up an entry resolved for the program.
+    let offset = self
+      .locals
+      .keys()
+      .map(|token| token.span_end)
+      .max()
+      .unwrap_or(0);
+    for token in &mut scanner.tokens {
+      token.span_start += offset;
+      token.span_end += offset;
+    }
+
+    let mut parser = Parser::new(TokenStream::new(scanner.tokens));
+    let Some(expr) = parser.parse_expression(&mut engine) else {
+      return Err(Self::unshift(engine, offset));
+    };
+
+    let mut resolver = Resolver::new();
+    resolver.run(&vec![Stmt::Expr(expr.clone())], &mut engine);
+    if !engine.proceed() {
+      return Err(Self::unshift(engine, offset));
+    }
+
+    // The locals of the last program stay, the functions it defined resolve their bodies with
+    // them.
+    let mut locals = self.locals.clone();
+    locals.extend(resolver.get_locals().clone());
+
+    let program_locals = std::mem::replace(&mut self.locals, locals);
+    let mut env = self.env.clone();
+    let result = self.eval_expr(&expr, &mut env, &mut engine);
+    self.locals = program_locals;
+
+    match result {
+      Ok((value, _)) => Ok(value),
+      Err(error) => {
+        // Like in `run`, only the errors that didn't emit a diagnostic are reported here.
+        if !engine.has_errors() {
+          let message = match error {
+            InterpreterError::RuntimeError(message) => message,
+            error => error.to_string(),
+          };
+          engine.emit(Diagnostic::new(DiagnosticCode::RuntimeError, message));
+        }
+        Err(Self::unshift(engine, offset))
+      },
+    }
+  }
+
+  /// Function that moves the byte offsets of the expression's diagnostics back by the `offset`
+  /// its tokens were moved by in `evaluate_expression`, so they point into its source again.
+  fn unshift(engine: DiagnosticEngine, offset: usize) -> Vec<Diagnostic> {
+    let mut diagnostics = engine.into_diagnostics();
+    for label in diagnostics
+      .iter_mut()
+      .flat_map(|diagnostic| &mut diagnostic.labels)
+    {
+      label.span.byte_offset = label.span.byte_offset.saturating_sub(offset);
+    }
+    diagnostics
+  }
+
+  /// Function that returns a copy of every global defined so far. Arrays, maps and instances are
+  /// copied all the way down, so the snapshot doesn't change when the program mutates them later.
+  /// Functions, classes and natives stay as the handles the program holds, they can't be mutated.
+  ///
+  /// A container that holds itself can't be copied, see `deepcopy`. The snapshot shares it with
+  /// the program, so later mutations of it do show through.
+  pub fn snapshot_globals(&self) -> HashMap<String, LoxValue> {
+    self
+      .env
+      .borrow()
+      .values
+      .iter()
+      .map(|(name, value)| {
+        let value = DeepcopyFunction::copy(value).unwrap_or_else(|_| value.clone());
+        (name.clone(), value)
+      })
+      .collect()
+  }
+
+  /// Function that defines the global class `name` from the host. Its instances start with
+  /// `fields` and each method is called with `this` as its first argument, the arity a method
+  /// reports doesn't count `this`. A method named `init` runs when the class is called.
+  pub fn define_class(
+    &mut self,
+    name: &str,
+    fields: &[(&str, LoxValue)],
+    methods: Vec<(&str, Box<dyn LoxCallable>)>,
+  ) {
+    let class = LoxClass {
+      name: name.to_string(),
+      superclass: LoxValue::Nil,
+      methods: HashMap::new(),
+      static_methods: HashMap::new(),
+      native_methods: methods
+        .into_iter()
+        .map(|(name, method)| (name.to_string(), Rc::from(method)))
+        .collect(),
+      field_defaults: fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect(),
+    };
+
+    self
+      .env
+      .borrow_mut()
+      .define(name.to_string(), LoxValue::Class(Rc::new(class)));
   }
 
   pub fn run(
     &mut self,
     ast: Vec<Stmt>,
-    locals: HashMap<String, usize>,
+    locals: HashMap<Token, usize>,
     engine: &mut DiagnosticEngine,
   ) {
-    PrintFunction::add(self);
-    ClockFunction::add(self);
     self.locals = locals;
 
     let mut env = self.env.clone();
-    for stmt in ast {
-      let _ = self.eval_stmt(stmt, &mut env, engine);
+    for stmt in &ast {
+      let error_count = engine.error_count();
+      let reported = engine.get_diagnostics().len();
+
+      if let Err(InterpreterError::RuntimeError(message)) = self.eval_stmt(stmt, &mut env, engine) {
+        // Most runtime errors already emitted a diagnostic pointing at the source, only the
+        // ones that didn't are reported here.
+        if engine.error_count() == error_count {
+          engine.emit(Diagnostic::new(DiagnosticCode::RuntimeError, message));
+        }
+      }
+      self.write_errors(&engine.get_diagnostics()[reported..]);
     }
     self.env = env;
   }
 
+  /// Function that writes the errors among `diagnostics` to the error output, if the builder set
+  /// one. A writer that fails is skipped, the errors are still in the `DiagnosticEngine`.
+  fn write_errors(&self, diagnostics: &[Diagnostic]) {
+    let Some(error_output) = &self.error_output else {
+      return;
+    };
+
+    let mut error_output = error_output.borrow_mut();
+    for diagnostic in diagnostics {
+      if diagnostic.severity == Severity::Error {
+        let _ = writeln!(error_output, "{}", diagnostic);
+      }
+    }
+  }
+
+  /// Function that asks the import resolver for the source of the module named `path`, an error
+  /// when the builder set no resolver.
+  pub fn resolve_import(&self, path: &str) -> Result<String, InterpreterError> {
+    match &self.import_resolver {
+      Some(import_resolver) => import_resolver.resolve(path),
+      None => Err(InterpreterError::RuntimeError(format!(
+        "Cannot import '{}', no import resolver is set",
+        path
+      ))),
+    }
+  }
+
   pub fn eval_stmt(
     &mut self,
-    stmt: Stmt,
+    stmt: &Stmt,
     env: &mut Rc<RefCell<Env>>,
     engine: &mut DiagnosticEngine,
   ) -> Result<(), InterpreterError> {
     match stmt {
       Stmt::Expr(expr) => {
         self.eval_expr(expr, env, engine)?;
-        return Ok(());
+        Ok(())
       },
       Stmt::VarDecl(identifier_token, expr) => match expr {
         Some(expr) => {
           let (expr_value, _) = self.eval_expr(expr, env, engine)?;
-          env.borrow_mut().define(identifier_token.lexeme, expr_value);
-          return Ok(());
+          env
+            .borrow_mut()
+            .define(identifier_token.lexeme.to_string(), expr_value);
+          Ok(())
         },
         None => {
           env
             .borrow_mut()
-            .define(identifier_token.lexeme, LoxValue::Nil);
-          return Ok(());
+            .define(identifier_token.lexeme.to_string(), LoxValue::Nil);
+          Ok(())
         },
       },
       Stmt::Block(block) => {
         self.eval_block(block, env, engine)?;
-        return Ok(());
+        Ok(())
       },
       Stmt::If(condition, then_branch, else_branch) => {
-        self.eval_if(env, *condition, *then_branch, else_branch, engine)?;
-        return Ok(());
+        self.eval_if(env, condition, then_branch, else_branch.as_deref(), engine)?;
+        Ok(())
       },
       Stmt::While(condition, stmt) => {
-        self.eval_while(env, *condition, *stmt, engine)?;
-        return Ok(());
+        self.eval_while(env, condition, stmt, engine)?;
+        Ok(())
       },
       Stmt::Fun(name, params, body) => {
-        self.eval_fun(env, name, params, *body, engine)?;
-        return Ok(());
+        self.eval_fun(env, name, params, body, engine)?;
+        Ok(())
       },
       Stmt::Return(name, _) => {
         let diagnostic = Diagnostic::new(
@@ -101,12 +395,9 @@ impl Interpreter {
         ));
 
         engine.emit(diagnostic);
-        return Ok(());
+        Ok(())
       },
       Stmt::Break(token) => {
-        let mut token = token;
-        token.position.0 -= 1;
-        token.position.1 += 7;
         // At top level, this is an error
         let diagnostic = Diagnostic::new(
           DiagnosticCode::BreakOutsideLoop,
@@ -138,7 +429,14 @@ impl Interpreter {
         Ok(())
       },
       Stmt::Class(name, superclass, methods, static_methods) => {
-        self.eval_class(env, name, superclass, *methods, *static_methods, engine)?;
+        self.eval_class(
+          env,
+          name,
+          superclass.as_ref(),
+          methods,
+          static_methods,
+          engine,
+        )?;
         Ok(())
       },
     }
@@ -147,17 +445,18 @@ impl Interpreter {
   fn eval_class(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    name: Expr,
-    superclass: Option<Expr>,
-    methods: Vec<Stmt>,
-    static_methods: Vec<Stmt>,
+    name: &Expr,
+    superclass: Option<&Expr>,
+    methods: &[Stmt],
+    static_methods: &[Stmt],
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let class_name = match name {
-      Expr::Identifier(token) => token.lexeme.clone(),
+      Expr::Identifier(token) => token.lexeme.to_string(),
       _ => {
-        eprintln!("Class name must be an identifier");
-        return Err(InterpreterError::RuntimeError);
+        return Err(InterpreterError::RuntimeError(
+          "Class name must be an identifier".to_string(),
+        ));
       },
     };
 
@@ -198,7 +497,9 @@ impl Interpreter {
         .with_help("Superclass must be a class".to_string());
 
         engine.emit(diagnostic);
-        return Err(InterpreterError::RuntimeError);
+        return Err(InterpreterError::RuntimeError(
+          "Superclass must be a class".to_string(),
+        ));
       }
     };
 
@@ -210,11 +511,13 @@ impl Interpreter {
     // Static methods are resolved outside the super environment (use the original `env` or its enclosing)
     self.eval_method_map(env, static_methods, &mut static_methods_map, engine);
 
-    let class = Arc::new(LoxClass {
+    let class = Rc::new(LoxClass {
       name: class_name.clone(),
       superclass: super_class_val,
       methods: methods_map,
       static_methods: static_methods_map,
+      native_methods: HashMap::new(),
+      field_defaults: vec![],
     });
 
     // Assign the actual class object to the name we defined earlier (overwriting LoxValue::Nil)
@@ -226,14 +529,14 @@ impl Interpreter {
   fn eval_return(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    _name: Token,
-    value: Option<Expr>,
+    _name: &Token,
+    value: Option<&Expr>,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     match value {
-      Some(expr) => match self.eval_expr(expr, env, engine) {
-        Ok((expr_value, _)) => Err(InterpreterError::Return(expr_value)),
-        Err(_) => Err(InterpreterError::Return(LoxValue::Nil)),
+      Some(expr) => {
+        let (expr_value, _) = self.eval_expr(expr, env, engine)?;
+        Err(InterpreterError::Return(expr_value))
       },
 
       None => Err(InterpreterError::Return(LoxValue::Nil)),
@@ -243,34 +546,34 @@ impl Interpreter {
   fn eval_method_map(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    methods: Vec<Stmt>,
-    methods_map: &mut HashMap<String, Arc<LoxFunction>>,
-    engine: &mut DiagnosticEngine,
+    methods: &[Stmt],
+    methods_map: &mut HashMap<String, Rc<LoxFunction>>,
+    _engine: &mut DiagnosticEngine,
   ) {
     for method in methods {
       match method {
         Stmt::Fun(name, params, body) => {
           // Extract method name
           let method_name = match name {
-            Expr::Identifier(token) => token.lexeme.clone(),
+            Expr::Identifier(token) => token.lexeme.to_string(),
             _ => continue,
           };
 
           // Extract parameters
           let params_names: Vec<Token> = params
-            .into_iter()
+            .iter()
             .filter_map(|expr| match expr {
-              Expr::Identifier(token) => Some(token),
+              Expr::Identifier(token) => Some(token.clone()),
               _ => None,
             })
             .collect();
           let is_initializer = method_name == "init";
 
           // Create LoxFunction for this method
-          let function = Arc::new(LoxFunction {
+          let function = Rc::new(LoxFunction {
             params: params_names,
-            body: match *body {
-              Stmt::Block(stmts) => *stmts,
+            body: match body.as_ref() {
+              Stmt::Block(stmts) => stmts.to_vec(),
               _ => vec![],
             },
             closure: env.clone(), // Capture current environment
@@ -289,39 +592,39 @@ impl Interpreter {
   fn eval_fun(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    name: Expr,
-    params: Vec<Expr>,
-    body: Stmt,
+    name: &Expr,
+    params: &[Expr],
+    body: &Stmt,
     _engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let name = match name {
-      Expr::Identifier(token) => token.lexeme.clone(),
+      Expr::Identifier(token) => token.lexeme.to_string(),
       _ => {
-        eprintln!("Function name must be an identifier");
-        return Err(InterpreterError::RuntimeError);
+        return Err(InterpreterError::RuntimeError(
+          "Function name must be an identifier".to_string(),
+        ));
       },
     };
 
     let params_names = params
-      .into_iter()
+      .iter()
       .map(|expr| match expr {
-        Expr::Identifier(token) => Ok(token),
-        _ => Err(InterpreterError::RuntimeError),
+        Expr::Identifier(token) => Ok(token.clone()),
+        _ => Err(InterpreterError::RuntimeError(
+          "Function parameters must be identifiers".to_string(),
+        )),
       })
       .collect::<Result<Vec<_>, _>>()?;
 
-    match body {
-      Stmt::Block(body) => {
-        let function = Arc::new(LoxFunction {
-          params: params_names,
-          body: *body,
-          closure: env.borrow().enclosing.clone().unwrap_or(env.clone()),
-          is_initializer: false,
-        });
+    if let Stmt::Block(body) = body {
+      let function = Rc::new(LoxFunction {
+        params: params_names,
+        body: body.to_vec(),
+        closure: env.borrow().enclosing.clone().unwrap_or(env.clone()),
+        is_initializer: false,
+      });
 
-        env.borrow_mut().define(name, LoxValue::Function(function));
-      },
-      _ => {},
+      env.borrow_mut().define(name, LoxValue::Function(function));
     };
 
     Ok((LoxValue::Nil, None))
@@ -330,19 +633,19 @@ impl Interpreter {
   fn eval_while(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    condition: Expr,
-    stmt: Stmt,
+    condition: &Expr,
+    stmt: &Stmt,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     loop {
-      let (condition_val, _) = self.eval_expr(condition.clone(), env, engine)?;
+      let (condition_val, _) = self.eval_expr(condition, env, engine)?;
 
       if !self.is_truthy(&condition_val) {
         break;
       }
 
       // Execute the body and handle break/continue
-      match self.eval_stmt(stmt.clone(), env, engine) {
+      match self.eval_stmt(stmt, env, engine) {
         Ok(_) => continue,                           // Normal execution, continue loop
         Err(InterpreterError::Break) => break,       // Break out of loop
         Err(InterpreterError::Continue) => continue, // Continue to next iteration
@@ -356,9 +659,9 @@ impl Interpreter {
   fn eval_if(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    condition: Expr,
-    then_branch: Stmt,
-    else_branch: Option<Box<Stmt>>,
+    condition: &Expr,
+    then_branch: &Stmt,
+    else_branch: Option<&Stmt>,
     engine: &mut DiagnosticEngine,
   ) -> Result<(), InterpreterError> {
     let (expr_val, token) = self.eval_expr(condition, env, engine)?;
@@ -369,7 +672,7 @@ impl Interpreter {
           self.eval_stmt(then_branch, env, engine)?;
         } else {
           if let Some(else_branch) = else_branch {
-            self.eval_stmt(*else_branch, env, engine)?;
+            self.eval_stmt(else_branch, env, engine)?;
           }
         }
         Ok(())
@@ -382,14 +685,16 @@ impl Interpreter {
           "If condition must be a boolean",
           &format!("Expected boolean, found {}", &expr_val.to_string()),
         )?;
-        Err(InterpreterError::RuntimeError)
+        Err(InterpreterError::RuntimeError(
+          "If condition must be a boolean".to_string(),
+        ))
       },
     }
   }
 
   pub fn eval_block(
     &mut self,
-    block: Box<Vec<Stmt>>,
+    block: &[Stmt],
     env: &mut Rc<RefCell<Env>>,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
@@ -397,19 +702,19 @@ impl Interpreter {
       env.borrow_mut().with_enclosing(Rc::clone(env)),
     ));
 
-    for stmt in *block {
+    for stmt in block {
       match stmt {
         Stmt::VarDecl(identifier_token, expr) => match expr {
           Some(expr) => {
             let (expr_value, _) = self.eval_expr(expr, &mut enclosing_env, engine)?;
             enclosing_env
               .borrow_mut()
-              .define(identifier_token.lexeme, expr_value);
+              .define(identifier_token.lexeme.to_string(), expr_value);
           },
           None => {
             enclosing_env
               .borrow_mut()
-              .define(identifier_token.lexeme, LoxValue::Nil);
+              .define(identifier_token.lexeme.to_string(), LoxValue::Nil);
           },
         },
         Stmt::Expr(expr) => {
@@ -421,29 +726,36 @@ impl Interpreter {
         Stmt::If(condition, then_branch, else_branch) => {
           self.eval_if(
             &mut enclosing_env,
-            *condition,
-            *then_branch,
-            else_branch,
+            condition,
+            then_branch,
+            else_branch.as_deref(),
             engine,
           )?;
         },
         Stmt::While(condition, stmt) => {
-          self.eval_while(&mut enclosing_env, *condition, *stmt, engine)?;
+          self.eval_while(&mut enclosing_env, condition, stmt, engine)?;
         },
         Stmt::Fun(name, params, body) => {
-          self.eval_fun(&mut enclosing_env, name, params, *body, engine)?;
+          self.eval_fun(&mut enclosing_env, name, params, body, engine)?;
         },
         Stmt::Return(name, value) => {
-          self.eval_return(&mut enclosing_env, name, value, engine)?;
+          self.eval_return(&mut enclosing_env, name, value.as_ref(), engine)?;
         },
-        Stmt::Break(token) => {
+        Stmt::Break(_token) => {
           return Err(InterpreterError::Break);
         },
-        Stmt::Continue(token) => {
+        Stmt::Continue(_token) => {
           return Err(InterpreterError::Continue);
         },
         Stmt::Class(name, superclass, methods, static_methods) => {
-          self.eval_class(env, name, superclass, *methods, *static_methods, engine)?;
+          self.eval_class(
+            env,
+            name,
+            superclass.as_ref(),
+            methods,
+            static_methods,
+            engine,
+          )?;
         },
       }
     }
@@ -453,37 +765,66 @@ impl Interpreter {
 
   fn eval_expr(
     &mut self,
-    expr: Expr,
+    expr: &Expr,
     env: &mut Rc<RefCell<Env>>,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     match expr {
       Expr::Literal(token) => self.eval_literal(token, engine),
-      Expr::Grouping(expr) => self.eval_grouping(env, *expr, engine),
-      Expr::Unary { operator, rhs } => self.eval_unary(env, operator, *rhs, engine),
-      Expr::Binary { lhs, operator, rhs } => self.eval_binary(env, *lhs, operator, *rhs, engine),
+      Expr::Grouping(expr) => self.eval_grouping(env, expr, engine),
+      Expr::Array { bracket, elements } => {
+        let mut values = Vec::with_capacity(elements.len());
+        for element in elements {
+          values.push(self.eval_expr(element, env, engine)?.0);
+        }
+        Ok((LoxValue::array(values), Some(bracket.clone())))
+      },
+      Expr::Map { brace, entries } => {
+        let mut map = MapEntries::with_capacity(entries.len());
+        for (key, value) in entries {
+          let (key, _) = self.eval_expr(key, env, engine)?;
+          let (value, _) = self.eval_expr(value, env, engine)?;
+          map.insert(key_of(&key, engine)?, (key, value));
+        }
+        Ok((LoxValue::map(map), Some(brace.clone())))
+      },
+      Expr::Template { backtick, parts } => {
+        let mut text = String::new();
+        for part in parts {
+          text.push_str(&self.eval_expr(part, env, engine)?.0.to_lox_string());
+        }
+        Ok((LoxValue::String(text), Some(backtick.clone())))
+      },
+      Expr::Unary { operator, rhs } => self.eval_unary(env, operator, rhs, engine),
+      Expr::Binary { lhs, operator, rhs } => self.eval_binary(env, lhs, operator, rhs, engine),
+      Expr::Logical { lhs, operator, rhs } => self.eval_logical(env, operator, lhs, rhs, engine),
       Expr::Ternary {
         condition,
         then_branch,
         else_branch,
-      } => self.eval_ternary(env, *condition, *then_branch, *else_branch, engine),
-      Expr::Assign { name, value } => self.eval_assign(name, *value, env, engine),
+      } => self.eval_ternary(env, condition, then_branch, else_branch, engine),
+      Expr::Assign { name, value } => self.eval_assign(name, value, env, engine),
       Expr::Identifier(token) => self.eval_identifier(token, env, engine),
       Expr::Call {
         callee,
         paren,
         arguments,
-      } => match self.eval_call(env, *callee, paren, arguments, engine) {
+      } => match self.eval_call(env, callee, paren, arguments, engine) {
         Ok(v) => Ok(v),
         Err(InterpreterError::Return(v)) => Ok((v, None)),
-        _ => Err(InterpreterError::RuntimeError),
+        Err(InterpreterError::RuntimeError(message)) => {
+          Err(InterpreterError::RuntimeError(message))
+        },
+        Err(_) => Err(InterpreterError::RuntimeError(
+          "'break' and 'continue' can't cross a function call".to_string(),
+        )),
       },
-      Expr::Get { object, name } => self.eval_get(env, *object, name, engine),
+      Expr::Get { object, name } => self.eval_get(env, object, name, engine),
       Expr::Set {
         object,
         name,
         value,
-      } => self.eval_set(env, *object, name, *value, engine),
+      } => self.eval_set(env, object, name, value, engine),
       Expr::This(token) => self.eval_identifier(token, env, engine),
       Expr::Super(token, name) => self.eval_super_expr(token, name, env),
     }
@@ -491,26 +832,30 @@ impl Interpreter {
 
   fn eval_super_expr(
     &mut self,
-    keyword: Token,
-    name: Token,
+    keyword: &Token,
+    name: &Token,
     env: &mut Rc<RefCell<Env>>,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     // The Resolver guaranteed this is in `self.locals`.
     let &distance = self
       .locals
-      .get(&keyword.lexeme)
-      .ok_or(InterpreterError::RuntimeError)?; // Should not fail if resolved
+      .get(keyword)
+      .ok_or_else(|| InterpreterError::RuntimeError("'super' was not resolved".to_string()))?;
 
     // 1. Look up "super" (the superclass object) at the resolved distance.
     let superclass_val = env
       .borrow_mut()
       .get_at(distance, "super")
-      .ok_or(InterpreterError::RuntimeError)?
+      .ok_or_else(|| InterpreterError::RuntimeError("'super' is not defined".to_string()))?
       .clone();
 
     let superclass = match superclass_val {
       LoxValue::Class(c) => c,
-      _ => return Err(InterpreterError::RuntimeError), // Should be a class
+      _ => {
+        return Err(InterpreterError::RuntimeError(
+          "'super' must refer to a class".to_string(),
+        ))
+      },
     };
 
     // 2. Look up "this" (the instance object) one environment closer.
@@ -518,32 +863,35 @@ impl Interpreter {
     let instance_val = env
       .borrow_mut()
       .get_at(distance - 1, "this")
-      .ok_or(InterpreterError::RuntimeError)?
+      .ok_or_else(|| InterpreterError::RuntimeError("'this' is not defined".to_string()))?
       .clone();
 
     let instance = match instance_val {
       LoxValue::Instance(i) => i,
-      _ => return Err(InterpreterError::RuntimeError), // Should be an instance
+      _ => {
+        return Err(InterpreterError::RuntimeError(
+          "'this' must refer to an instance".to_string(),
+        ))
+      },
     };
 
     // 3. Find the method starting from the superclass.
     // Use the LoxClass::find_method which recursively searches superclasses.
     let method = superclass.find_method(&name.lexeme).ok_or_else(|| {
-      eprintln!("Undefined property '{}'", name.lexeme);
-      InterpreterError::RuntimeError
+      InterpreterError::RuntimeError(format!("Undefined property '{}'", name.lexeme))
     })?;
 
     // 4. Bind the method to the current instance (`this`).
     let bound_method = method.bind(instance.clone());
 
-    Ok((LoxValue::Function(bound_method), Some(name)))
+    Ok((LoxValue::Function(bound_method), Some(name.clone())))
   }
 
   fn eval_get(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    object: Expr,
-    name: Token,
+    object: &Expr,
+    name: &Token,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (object_val, _) = self.eval_expr(object, env, engine)?;
@@ -551,46 +899,126 @@ impl Interpreter {
     // ADD THIS HERE - Check if accessing a class (for static methods)
     if let LoxValue::Class(class) = object_val {
       // Accessing static method: MyClass.staticMethod()
-      if let Some(static_method) = class.static_methods.get(&name.lexeme) {
+      if let Some(static_method) = class.static_methods.get(name.lexeme.as_ref()) {
         // Don't bind 'this' - static methods have no instance context
-        return Ok((LoxValue::Function(static_method.clone()), Some(name)));
+        return Ok((
+          LoxValue::Function(static_method.clone()),
+          Some(name.clone()),
+        ));
       }
 
-      eprintln!("Undefined static method '{}'", name.lexeme);
-      return Err(InterpreterError::RuntimeError);
+      return Err(InterpreterError::RuntimeError(format!(
+        "Undefined static method '{}'",
+        name.lexeme
+      )));
+    }
+
+    if let LoxValue::Array(array) = object_val {
+      return match ArrayMethod::bind(&array, &name.lexeme) {
+        Some(method) => Ok((method, Some(name.clone()))),
+        None => Err(InterpreterError::RuntimeError(format!(
+          "Undefined array method '{}'",
+          name.lexeme
+        ))),
+      };
+    }
+
+    if let LoxValue::NativeClass(class) = object_val {
+      return match class.static_methods.get(name.lexeme.as_ref()) {
+        Some(method) => Ok((method.clone(), Some(name.clone()))),
+        None => Err(InterpreterError::RuntimeError(format!(
+          "Undefined static method '{}'",
+          name.lexeme
+        ))),
+      };
+    }
+
+    if let LoxValue::Number(number) = object_val {
+      return match NumberMethod::bind(number, &name.lexeme) {
+        Some(method) => Ok((method, Some(name.clone()))),
+        None => Err(InterpreterError::RuntimeError(format!(
+          "Undefined number method '{}'",
+          name.lexeme
+        ))),
+      };
+    }
+
+    if let LoxValue::String(string) = &object_val {
+      return match StringMethod::bind(string, &name.lexeme) {
+        Some(method) => Ok((method, Some(name.clone()))),
+        None => Err(InterpreterError::RuntimeError(format!(
+          "Undefined string method '{}'",
+          name.lexeme
+        ))),
+      };
+    }
+
+    if let LoxValue::Map(map) = object_val {
+      return match MapMethod::bind(&map, &name.lexeme) {
+        Some(method) => Ok((method, Some(name.clone()))),
+        None => Err(InterpreterError::RuntimeError(format!(
+          "Undefined map method '{}'",
+          name.lexeme
+        ))),
+      };
+    }
+
+    if let LoxValue::WeakRef(weak) = &object_val {
+      return match WeakRefMethod::bind(weak, &name.lexeme) {
+        Some(method) => Ok((method, Some(name.clone()))),
+        None => Err(InterpreterError::RuntimeError(format!(
+          "Undefined weakref method '{}'",
+          name.lexeme
+        ))),
+      };
     }
 
     if let LoxValue::Instance(instance) = object_val {
-      if let Some(field) = instance.borrow().fields.get(&name.lexeme) {
-        return Ok((field.clone(), Some(name)));
+      if let Some(field) = instance.borrow().get_field(&name.lexeme) {
+        return Ok((field, Some(name.clone())));
       }
 
       if let Some(method) = instance.borrow().class.find_method(&name.lexeme) {
         // Bind 'this' to the instance, regardless of which class defined the method
         let bound_method = method.bind(instance.clone());
-        return Ok((LoxValue::Function(bound_method), Some(name)));
+        return Ok((LoxValue::Function(bound_method), Some(name.clone())));
       }
 
       // Check methods and bind 'this'
-      if let Some(method) = instance.borrow().class.methods.get(&name.lexeme) {
+      if let Some(method) = instance.borrow().class.methods.get(name.lexeme.as_ref()) {
         let bound_method = method.bind(instance.clone());
-        return Ok((LoxValue::Function(bound_method), Some(name)));
+        return Ok((LoxValue::Function(bound_method), Some(name.clone())));
+      }
+
+      if let Some(method) = instance.borrow().class.find_native_method(&name.lexeme) {
+        let bound_method = BoundNativeMethod {
+          this: instance.clone(),
+          method,
+        };
+        return Ok((
+          LoxValue::NativeFunction(Rc::new(bound_method)),
+          Some(name.clone()),
+        ));
       }
 
-      eprintln!("Undefined property '{}'", name.lexeme);
-      return Err(InterpreterError::RuntimeError);
+      return Err(InterpreterError::RuntimeError(format!(
+        "Undefined property '{}'",
+        name.lexeme
+      )));
     }
 
-    eprintln!("Cannot read property '{}' of non-instance", name.lexeme);
-    return Err(InterpreterError::RuntimeError);
+    Err(InterpreterError::RuntimeError(format!(
+      "Cannot read property '{}' of non-instance",
+      name.lexeme
+    )))
   }
 
   fn eval_set(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    object: Expr,
-    name: Token,
-    value: Expr,
+    object: &Expr,
+    name: &Token,
+    value: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (object_val, _) = self.eval_expr(object, env, engine)?;
@@ -602,21 +1030,22 @@ impl Interpreter {
       instance
         .borrow_mut()
         .fields
-        .insert(name.lexeme.clone(), value_result.clone());
+        .insert(name.lexeme.to_string(), value_result.clone());
 
-      return Ok((value_result, Some(name)));
+      return Ok((value_result, Some(name.clone())));
     }
 
-    eprintln!("Only instances have fields");
-    Err(InterpreterError::RuntimeError)
+    Err(InterpreterError::RuntimeError(
+      "Only instances have fields".to_string(),
+    ))
   }
 
   fn eval_call(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    callee: Expr,
-    paren: Token,
-    arguments: Vec<Expr>,
+    callee: &Expr,
+    paren: &Token,
+    arguments: &[Expr],
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let args_val = self.eval_args(env, arguments, engine)?;
@@ -624,88 +1053,89 @@ impl Interpreter {
 
     match callee_val {
       LoxValue::Function(fnc) => {
-        let mut token = token.unwrap();
-        token.position.0 += 1;
+        let token = token.unwrap();
 
         if args_val.len() != fnc.arity() {
           let args_space: usize = args_val
             .clone()
             .into_iter()
-            .map(|(_, v)| v.unwrap().lexeme.len())
+            .map(|(_, v)| v.unwrap().lexeme.chars().count())
             .sum();
 
-          let diagnostic = Diagnostic::new(
-            DiagnosticCode::WrongNumberOfArguments,
-            "Wrong number of arguments".to_string(),
-          )
-          .with_label(Label::primary(
-            token.to_span(),
-            Some(format!(
-              "wrong number of arguments, expected {} arguments but you passed {} arguments",
-              fnc.arity(),
-              args_val.len()
-            )),
-          ))
-          .with_label(Label::secondary(
-            Span {
-              length: (args_space + 2 as usize),
-              column: token.position.1 + 1,
-              ..token.to_span()
-            },
-            Some(format!("expected {} arguments here", fnc.arity())),
-          ));
+          let diagnostic = Diagnostic::from_code(DiagnosticCode::WrongNumberOfArguments)
+            .with_label(Label::primary(
+              token.to_span(),
+              Some(format!(
+                "wrong number of arguments, expected {} arguments but you passed {} arguments",
+                fnc.arity(),
+                args_val.len()
+              )),
+            ))
+            .with_label(Label::secondary(
+              Span {
+                length: (args_space + 2_usize),
+                column: token.position.1 + token.lexeme.chars().count(),
+                byte_offset: token.span_end,
+                ..token.to_span()
+              },
+              Some(format!("expected {} arguments here", fnc.arity())),
+            ));
           engine.emit(diagnostic);
 
-          return Err(InterpreterError::RuntimeError);
+          return Err(InterpreterError::RuntimeError(
+            "Wrong number of arguments".to_string(),
+          ));
         }
 
-        let result = fnc.call(self, args_val, engine)?;
-        return Ok((result, Some(paren)));
+        let result = self.call_tracked(fnc.as_ref(), Some(&token), args_val, engine)?;
+        Ok((result, Some(paren.clone())))
       },
       LoxValue::NativeFunction(fnc) => {
         if fnc.arity() != usize::MAX && args_val.len() != fnc.arity() {
-          return Err(InterpreterError::RuntimeError);
+          return Err(InterpreterError::RuntimeError(format!(
+            "Expected {} arguments but got {}",
+            fnc.arity(),
+            args_val.len()
+          )));
         }
 
-        let result = fnc.call(self, args_val, engine)?;
-        return Ok((result, Some(paren)));
+        let result = self.call_tracked(fnc.as_ref(), token.as_ref(), args_val, engine)?;
+        Ok((result, Some(paren.clone())))
       },
       LoxValue::Class(class) => {
         // Check arity
         if args_val.len() != class.arity() {
-          let mut token_copy = paren.clone();
-          token_copy.position.0 += 1;
+          let diagnostic = Diagnostic::from_code(DiagnosticCode::WrongNumberOfArguments)
+            .with_label(Label::primary(
+              paren.to_span(),
+              Some(format!(
+                "Expected {} arguments but got {}",
+                class.arity(),
+                args_val.len()
+              )),
+            ));
+          engine.emit(diagnostic);
 
-          let diagnostic = Diagnostic::new(
-            DiagnosticCode::WrongNumberOfArguments,
+          return Err(InterpreterError::RuntimeError(
             "Wrong number of arguments".to_string(),
-          )
-          .with_label(Label::primary(
-            token_copy.to_span(),
-            Some(format!(
-              "Expected {} arguments but got {}",
-              class.arity(),
-              args_val.len()
-            )),
           ));
-          engine.emit(diagnostic);
-
-          return Err(InterpreterError::RuntimeError);
         }
 
         // Call the class (which handles init() internally)
-        let result = class.call(self, args_val, engine)?;
+        let result = self.call_tracked(class.as_ref(), token.as_ref(), args_val, engine)?;
 
-        return Ok((result, Some(paren)));
+        Ok((result, Some(paren.clone())))
       },
-      _ => Err(InterpreterError::RuntimeError),
+      _ => Err(InterpreterError::RuntimeError(
+        "Can only call functions and classes".to_string(),
+      )),
     }
   }
 
   fn eval_args(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    arguments: Vec<Expr>,
+    arguments: &[Expr],
     engine: &mut DiagnosticEngine,
   ) -> Result<Vec<(LoxValue, Option<Token>)>, InterpreterError> {
     let mut args_val = vec![];
@@ -717,57 +1147,119 @@ impl Interpreter {
     Ok(args_val)
   }
 
+  /// Function that calls `callee` with already evaluated arguments, it lets native code such as
+  /// array methods call back into user functions.
+  pub fn call_value(
+    &mut self,
+    callee: &LoxValue,
+    arguments: Vec<LoxValue>,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let callable: &dyn LoxCallable = match callee {
+      LoxValue::Function(fnc) => fnc.as_ref(),
+      LoxValue::NativeFunction(fnc) => fnc.as_ref(),
+      LoxValue::Class(class) => class.as_ref(),
+      _ => {
+        return Err(InterpreterError::RuntimeError(format!(
+          "Can only call functions and classes, found {}",
+          callee.to_lox_string()
+        )))
+      },
+    };
+
+    if callable.arity() != usize::MAX && arguments.len() != callable.arity() {
+      return Err(InterpreterError::RuntimeError(format!(
+        "Expected {} arguments but got {}",
+        callable.arity(),
+        arguments.len()
+      )));
+    }
+
+    let arguments = arguments.into_iter().map(|value| (value, None)).collect();
+    self.call_tracked(callable, None, arguments, engine)
+  }
+
+  /// Function that calls `callable` under the call depth limit of the config, and records the
+  /// call in the profiler when there is one. `callee` is the token the call was written with.
+  fn call_tracked(
+    &mut self,
+    callable: &dyn LoxCallable,
+    callee: Option<&Token>,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    if let Some(max) = self.config.max_call_depth {
+      if self.call_depth >= max {
+        return Err(InterpreterError::RuntimeError(format!(
+          "Maximum call depth of {} exceeded",
+          max
+        )));
+      }
+    }
+
+    let start = Instant::now();
+    self.call_depth += 1;
+    let result = callable.call(self, arguments, engine);
+    self.call_depth -= 1;
+
+    if let Some(profiler) = &mut self.profiler {
+      let name = callee.map_or("<anonymous>", |token| token.lexeme.as_ref());
+      profiler.record(name, start.elapsed());
+    }
+    result
+  }
+
   fn eval_identifier(
     &self,
-    mut token: Token,
+    token: &Token,
     env: &mut Rc<RefCell<Env>>,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
-    if let Some(&depth) = self.locals.get(&token.lexeme) {
-      match env.borrow_mut().get_at(depth, &token.lexeme.as_str()) {
-        Some(v) => return Ok((v.clone(), Some(token))),
+    if let Some(&depth) = self.locals.get(token) {
+      match env.borrow_mut().get_at(depth, &token.lexeme) {
+        Some(v) => return Ok((v, Some(token.clone()))),
         None => {
-          eprintln!(
+          return Err(InterpreterError::RuntimeError(format!(
             "INTERNAL ERROR: Resolved variable '{}' not found at depth {}",
             token.lexeme, depth
-          );
-          return Err(InterpreterError::RuntimeError);
+          )));
         },
       }
     }
 
-    match env.borrow().get(&token.lexeme) {
-      Some(v) => Ok((v.clone(), Some(token))),
+    match env.borrow().get(token.lexeme.as_ref()) {
+      Some(v) => Ok((v, Some(token.clone()))),
       None => {
-        token.position.0 += 1;
-        token.position.1 -= 1;
         let diagnostic = Diagnostic::new(
           DiagnosticCode::UndeclaredVariable,
-          format!("Cannot assign to undeclared variable '{}'", token.lexeme),
+          format!("Undefined variable '{}'", token.lexeme),
         )
         .with_label(Label::primary(
           token.to_span(),
           Some("variable not declared".to_string()),
         ))
-        .with_help("Use 'var' to declare variables before assigning to them".to_string());
+        .with_help("Use 'var' to declare variables before reading them".to_string());
 
         engine.emit(diagnostic);
-        Err(InterpreterError::RuntimeError)
+        Err(InterpreterError::RuntimeError(format!(
+          "Undefined variable '{}'",
+          token.lexeme
+        )))
       },
     }
   }
 
   fn eval_assign(
     &mut self,
-    mut name: Token,
-    value: Expr,
+    name: &Token,
+    value: &Expr,
     env: &mut Rc<RefCell<Env>>,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (value, token) = self.eval_expr(value, env, engine)?;
 
     // Check if we have a resolved depth
-    if let Some(&depth) = self.locals.get(&name.lexeme) {
+    if let Some(&depth) = self.locals.get(name) {
       if env
         .borrow_mut()
         .assign_at(depth, &name.lexeme, value.clone())
@@ -777,8 +1269,6 @@ impl Interpreter {
     }
 
     if !env.borrow_mut().assign(&name.lexeme, value.clone()) {
-      name.position.0 += 1;
-      name.position.1 -= 1;
       let diagnostic = Diagnostic::new(
         DiagnosticCode::UndeclaredVariable,
         format!("Cannot assign to undeclared variable '{}'", name.lexeme),
@@ -790,7 +1280,10 @@ impl Interpreter {
       .with_help("Use 'var' to declare variables before assigning to them".to_string());
 
       engine.emit(diagnostic);
-      return Err(InterpreterError::RuntimeError);
+      return Err(InterpreterError::RuntimeError(format!(
+        "Cannot assign to undeclared variable '{}'",
+        name.lexeme
+      )));
     }
 
     Ok((value, token))
@@ -799,9 +1292,9 @@ impl Interpreter {
   fn eval_ternary(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    condition: Expr,
-    then_branch: Expr,
-    else_branch: Expr,
+    condition: &Expr,
+    then_branch: &Expr,
+    else_branch: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (condition_val, _) = self.eval_expr(condition, env, engine)?;
@@ -816,23 +1309,24 @@ impl Interpreter {
   fn eval_binary(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    lhs: Expr,
-    operator: Token,
-    rhs: Expr,
+    lhs: &Expr,
+    operator: &Token,
+    rhs: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
-    match operator.lexeme.as_str() {
+    match operator.lexeme.as_ref() {
       "%" | "*" | "/" | "-" => self.eval_arithmetic(env, operator, lhs, rhs, engine),
       "+" => self.eval_addition(env, operator, lhs, rhs, engine),
       "==" | "!=" => self.eval_equality(env, operator, lhs, rhs, engine),
       ">" | ">=" | "<" | "<=" => self.eval_comparison(env, operator, lhs, rhs, engine),
-      "||" | "&&" => self.eval_logical(env, operator, lhs, rhs, engine),
-      "," => Err(InterpreterError::RuntimeError),
+      "," => Err(InterpreterError::RuntimeError(
+        "Unexpected ',' operator".to_string(),
+      )),
       _ => self.emit_error(
         engine,
         DiagnosticCode::InvalidOperator,
         &format!("Unknown binary operator '{}'", operator.lexeme),
-        &operator,
+        operator,
         "This operator is not supported",
         Some("Valid operators are: +, -, %, *, /, ==, !=, <, <=, >, >="),
       ),
@@ -842,17 +1336,17 @@ impl Interpreter {
   fn eval_logical(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    operator: Token,
-    lhs: Expr,
-    rhs: Expr,
+    operator: &Token,
+    lhs: &Expr,
+    rhs: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (lhs_val, lhs_token) = self.eval_expr(lhs, env, engine)?;
 
     let is_truthy = self.is_truthy(&lhs_val);
 
-    match operator.lexeme.as_str() {
-      "||" => {
+    match operator.lexeme.as_ref() {
+      "||" | "or" => {
         // short-circuit: if lhs is truthy, return it
         if is_truthy {
           Ok((lhs_val, lhs_token))
@@ -860,7 +1354,7 @@ impl Interpreter {
           self.eval_expr(rhs, env, engine)
         }
       },
-      "&&" => {
+      "&&" | "and" => {
         // short-circuit: if lhs is falsy, return it
         if !is_truthy {
           Ok((lhs_val, lhs_token))
@@ -868,16 +1362,19 @@ impl Interpreter {
           self.eval_expr(rhs, env, engine)
         }
       },
-      _ => Err(InterpreterError::RuntimeError),
+      _ => Err(InterpreterError::RuntimeError(format!(
+        "Unknown logical operator '{}'",
+        operator.lexeme
+      ))),
     }
   }
 
   fn eval_arithmetic(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    operator: Token,
-    lhs: Expr,
-    rhs: Expr,
+    operator: &Token,
+    lhs: &Expr,
+    rhs: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (lhs_val, lhs_token) = self.eval_expr(lhs, env, engine)?;
@@ -885,7 +1382,7 @@ impl Interpreter {
 
     match (&lhs_val, &rhs_val) {
       (LoxValue::Number(a), LoxValue::Number(b)) => {
-        let result = match operator.lexeme.as_str() {
+        let result = match operator.lexeme.as_ref() {
           "%" => a % b,
           "*" => a * b,
           "/" => {
@@ -894,7 +1391,7 @@ impl Interpreter {
                 engine,
                 DiagnosticCode::DivisionByZero,
                 "Division by zero",
-                &operator,
+                operator,
                 "Cannot divide by zero",
                 "Consider checking if the divisor is zero before performing division",
                 rhs_token.as_ref(),
@@ -906,7 +1403,7 @@ impl Interpreter {
           "-" => a - b,
           _ => unreachable!(),
         };
-        Ok((LoxValue::Number(result), Some(operator)))
+        Ok((LoxValue::Number(result), Some(operator.clone())))
       },
       (LoxValue::Number(_), non_number) | (non_number, LoxValue::Number(_)) => {
         let (bad_token, bad_value) = if matches!(lhs_val, LoxValue::Number(_)) {
@@ -917,9 +1414,9 @@ impl Interpreter {
 
         self.emit_type_error(
           engine,
-          &operator,
+          operator,
           bad_token.as_ref(),
-          &format!("Arithmetic operations require numeric operands"),
+          "Arithmetic operations require numeric operands",
           &format!("Expected number, found {}", &bad_value.to_string()),
         )
       },
@@ -931,7 +1428,7 @@ impl Interpreter {
           &lhs.to_string(),
           &rhs.to_string()
         ),
-        &operator,
+        operator,
         "Both operands must be numbers",
         Some(&format!(
           "Left operand is {}, right operand is {}",
@@ -945,32 +1442,37 @@ impl Interpreter {
   fn eval_addition(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    operator: Token,
-    lhs: Expr,
-    rhs: Expr,
+    operator: &Token,
+    lhs: &Expr,
+    rhs: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (lhs_val, _) = self.eval_expr(lhs, env, engine)?;
     let (rhs_val, _) = self.eval_expr(rhs, env, engine)?;
 
     match (lhs_val, rhs_val) {
-      (LoxValue::Number(a), LoxValue::Number(b)) => Ok((LoxValue::Number(a + b), Some(operator))),
-      (LoxValue::String(a), LoxValue::String(b)) => {
-        Ok((LoxValue::String(format!("{}{}", a, b)), Some(operator)))
-      },
-      (LoxValue::String(a), LoxValue::Number(b)) => {
-        Ok((LoxValue::String(format!("{}{}", a, b)), Some(operator)))
-      },
-      (LoxValue::Number(a), LoxValue::String(b)) => {
-        Ok((LoxValue::String(format!("{}{}", a, b)), Some(operator)))
+      (LoxValue::Number(a), LoxValue::Number(b)) => {
+        Ok((LoxValue::Number(a + b), Some(operator.clone())))
       },
+      (LoxValue::String(a), LoxValue::String(b)) => Ok((
+        LoxValue::String(format!("{}{}", a, b)),
+        Some(operator.clone()),
+      )),
+      (LoxValue::String(a), LoxValue::Number(b)) => Ok((
+        LoxValue::String(format!("{}{}", a, b)),
+        Some(operator.clone()),
+      )),
+      (LoxValue::Number(a), LoxValue::String(b)) => Ok((
+        LoxValue::String(format!("{}{}", a, b)),
+        Some(operator.clone()),
+      )),
       (lhs, rhs) => self.emit_error(
         engine,
         DiagnosticCode::InvalidOperator,
         &format!("Cannot add {} and {}", &lhs.to_string(), &rhs.to_string()),
-        &operator,
+        operator,
         "Operands must be two numbers or at least one string",
-        Some(&format!("Try converting both operands to the same type")),
+        Some("Try converting both operands to the same type"),
       ),
     }
   }
@@ -978,28 +1480,28 @@ impl Interpreter {
   fn eval_equality(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    operator: Token,
-    lhs: Expr,
-    rhs: Expr,
+    operator: &Token,
+    lhs: &Expr,
+    rhs: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (lhs_val, _) = self.eval_expr(lhs, env, engine)?;
     let (rhs_val, _) = self.eval_expr(rhs, env, engine)?;
 
-    let result = match operator.lexeme.as_str() {
+    let result = match operator.lexeme.as_ref() {
       "==" => Self::is_equal(&lhs_val, &rhs_val),
       "!=" => !Self::is_equal(&lhs_val, &rhs_val),
       _ => unreachable!(),
     };
-    Ok((LoxValue::Bool(result), Some(operator)))
+    Ok((LoxValue::Bool(result), Some(operator.clone())))
   }
 
   fn eval_comparison(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    operator: Token,
-    lhs: Expr,
-    rhs: Expr,
+    operator: &Token,
+    lhs: &Expr,
+    rhs: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (lhs_val, _) = self.eval_expr(lhs, env, engine)?;
@@ -1007,14 +1509,14 @@ impl Interpreter {
 
     match (lhs_val, rhs_val) {
       (LoxValue::Number(a), LoxValue::Number(b)) => {
-        let result = match operator.lexeme.as_str() {
+        let result = match operator.lexeme.as_ref() {
           ">" => a > b,
           ">=" => a >= b,
           "<" => a < b,
           "<=" => a <= b,
           _ => unreachable!(),
         };
-        Ok((LoxValue::Bool(result), Some(operator)))
+        Ok((LoxValue::Bool(result), Some(operator.clone())))
       },
       (lhs, rhs) => self.emit_error(
         engine,
@@ -1024,9 +1526,9 @@ impl Interpreter {
           &lhs.to_string(),
           &rhs.to_string()
         ),
-        &operator,
+        operator,
         "Comparison operators require numeric operands",
-        Some(&format!("Both operands must be numbers for comparison")),
+        Some("Both operands must be numbers for comparison"),
       ),
     }
   }
@@ -1034,22 +1536,22 @@ impl Interpreter {
   fn eval_unary(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    operator: Token,
-    rhs: Expr,
+    operator: &Token,
+    rhs: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     let (rhs_val, rhs_token) = self.eval_expr(rhs, env, engine)?;
 
-    match operator.lexeme.as_str() {
+    match operator.lexeme.as_ref() {
       "!" => {
         let is_truthy = self.is_truthy(&rhs_val);
-        Ok((LoxValue::Bool(!is_truthy), Some(operator)))
+        Ok((LoxValue::Bool(!is_truthy), Some(operator.clone())))
       },
       "-" => match rhs_val {
-        LoxValue::Number(n) => Ok((LoxValue::Number(-n), Some(operator))),
+        LoxValue::Number(n) => Ok((LoxValue::Number(-n), Some(operator.clone()))),
         _ => self.emit_type_error(
           engine,
-          &operator,
+          operator,
           rhs_token.as_ref(),
           "Unary minus requires a numeric operand",
           &format!("Expected number, found {}", &rhs_val.to_string()),
@@ -1059,7 +1561,7 @@ impl Interpreter {
         engine,
         DiagnosticCode::InvalidUnaryOperator,
         &format!("Unknown unary operator '{}'", operator.lexeme),
-        &operator,
+        operator,
         "This operator is not supported as a unary operator",
         Some("Valid unary operators are: !, -"),
       ),
@@ -1069,7 +1571,7 @@ impl Interpreter {
   fn eval_grouping(
     &mut self,
     env: &mut Rc<RefCell<Env>>,
-    expr: Expr,
+    expr: &Expr,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     self.eval_expr(expr, env, engine)
@@ -1077,36 +1579,33 @@ impl Interpreter {
 
   fn eval_literal(
     &self,
-    token: Token,
+    token: &Token,
     engine: &mut DiagnosticEngine,
   ) -> Result<(LoxValue, Option<Token>), InterpreterError> {
     match token.literal {
       Literal::Number => match token.lexeme.parse::<f64>() {
-        Ok(num) => Ok((LoxValue::Number(num), Some(token))),
+        Ok(num) => Ok((LoxValue::Number(num), Some(token.clone()))),
         Err(_) => self.emit_error(
           engine,
           DiagnosticCode::InvalidNumber,
           &format!("Invalid number literal '{}'", token.lexeme),
-          &token,
+          token,
           "Failed to parse as a number",
           Some("Check that the number is formatted correctly"),
         ),
       },
-      Literal::String => Ok((LoxValue::String(token.lexeme.clone()), Some(token))),
-      Literal::Boolean => Ok((LoxValue::Bool(token.lexeme == "true"), Some(token))),
-      Literal::Nil => Ok((LoxValue::Nil, Some(token))),
+      Literal::String | Literal::Template => Ok((
+        LoxValue::String(token.lexeme.to_string()),
+        Some(token.clone()),
+      )),
+      Literal::Boolean => Ok((LoxValue::Bool(token.lexeme == "true"), Some(token.clone()))),
+      Literal::Nil => Ok((LoxValue::Nil, Some(token.clone()))),
     }
   }
 
   // Helper methods
   fn is_equal(a: &LoxValue, b: &LoxValue) -> bool {
-    match (a, b) {
-      (LoxValue::Nil, LoxValue::Nil) => true,
-      (LoxValue::Number(a), LoxValue::Number(b)) => a == b,
-      (LoxValue::String(a), LoxValue::String(b)) => a == b,
-      (LoxValue::Bool(a), LoxValue::Bool(b)) => a == b,
-      _ => false,
-    }
+    a == b
   }
 
   fn emit_error(
@@ -1126,7 +1625,7 @@ impl Interpreter {
     }
 
     engine.emit(diagnostic);
-    Err(InterpreterError::RuntimeError)
+    Err(InterpreterError::RuntimeError(message.to_string()))
   }
 
   fn emit_type_error(
@@ -1151,9 +1650,10 @@ impl Interpreter {
     }
 
     engine.emit(diagnostic);
-    Err(InterpreterError::RuntimeError)
+    Err(InterpreterError::RuntimeError(message.to_string()))
   }
 
+  #[allow(clippy::too_many_arguments)]
   fn emit_error_with_note(
     &self,
     engine: &mut DiagnosticEngine,
@@ -1180,11 +1680,11 @@ impl Interpreter {
     }
 
     engine.emit(diagnostic);
-    Err(InterpreterError::RuntimeError)
+    Err(InterpreterError::RuntimeError(message.to_string()))
   }
 
   fn is_truthy(&self, val: &LoxValue) -> bool {
-    return match &val {
+    match &val {
       LoxValue::Bool(b) => *b,
       LoxValue::Nil => false,
       LoxValue::Number(n) => *n != 0.0,
@@ -1192,7 +1692,11 @@ impl Interpreter {
       LoxValue::Function(_) => false,
       LoxValue::NativeFunction(_) => false,
       LoxValue::Class(_) => false,
+      LoxValue::NativeClass(_) => false,
       LoxValue::Instance(_) => false,
-    };
+      LoxValue::Array(a) => !a.borrow().is_empty(),
+      LoxValue::Map(m) => !m.borrow().is_empty(),
+      LoxValue::WeakRef(_) => false,
+    }
   }
 }
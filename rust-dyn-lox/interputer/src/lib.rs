@@ -0,0 +1,15 @@
+pub mod array;
+pub mod class;
+pub mod env;
+pub mod error;
+pub mod freezable;
+pub mod function;
+pub mod import;
+pub mod interpreter;
+pub mod lox_value;
+pub mod map;
+pub mod number;
+pub mod profiler;
+pub mod runner;
+pub mod string;
+pub mod weak;
@@ -1,14 +1,25 @@
-use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc, sync::Arc};
+use std::{
+  cell::{Ref, RefCell},
+  fmt, io,
+  num::{ParseFloatError, ParseIntError},
+  rc::Rc,
+};
+
+use unicode_normalization::UnicodeNormalization;
 
 use crate::{
-  class::{LoxClass, LoxClassInstance},
+  array::LoxArray,
+  class::{LoxClass, LoxClassInstance, NativeClass},
+  freezable::Freezable,
   function::{normal::LoxFunction, LoxCallable},
+  map::{LoxMap, MapEntries},
+  weak::WeakLoxValue,
 };
 
 #[derive(Debug)]
 pub enum InterpreterError {
   Return(LoxValue),
-  RuntimeError,
+  RuntimeError(String),
   Break,
   Continue,
 }
@@ -19,10 +30,14 @@ pub enum LoxValue {
   Number(f64),
   String(String),
   Bool(bool),
-  Function(Arc<LoxFunction>),
-  NativeFunction(Arc<dyn LoxCallable + Send + Sync>),
-  Class(Arc<LoxClass>),
+  Function(Rc<LoxFunction>),
+  NativeFunction(Rc<dyn LoxCallable>),
+  Class(Rc<LoxClass>),
+  NativeClass(Rc<NativeClass>),
   Instance(Rc<RefCell<LoxClassInstance>>),
+  Array(LoxArray),
+  Map(LoxMap),
+  WeakRef(WeakLoxValue),
 }
 
 impl fmt::Debug for LoxValue {
@@ -35,7 +50,163 @@ impl fmt::Debug for LoxValue {
       LoxValue::Function(_) => write!(f, "Function(<fn>)"),
       LoxValue::NativeFunction(_) => write!(f, "NativeFunction(<native>)"),
       LoxValue::Class(c) => write!(f, "Class({})", c.name),
+      LoxValue::NativeClass(c) => write!(f, "NativeClass({})", c.name),
       LoxValue::Instance(i) => write!(f, "Instance({})", i.borrow().class.name),
+      LoxValue::Array(a) => write!(f, "Array({:?})", a.borrow()),
+      LoxValue::Map(m) => write!(f, "Map({:?})", m.borrow().values().collect::<Vec<_>>()),
+      LoxValue::WeakRef(w) => match w.upgrade() {
+        Some(value) => write!(f, "WeakRef({value:?})"),
+        None => write!(f, "WeakRef(<dead>)"),
+      },
+    }
+  }
+}
+
+impl LoxValue {
+  /// Function that wraps `elements` in a new array value.
+  pub fn array(elements: Vec<LoxValue>) -> Self {
+    LoxValue::Array(Rc::new(Freezable::new(elements)))
+  }
+
+  /// Function that wraps `entries` in a new map value.
+  pub fn map(entries: MapEntries) -> Self {
+    LoxValue::Map(Rc::new(Freezable::new(entries)))
+  }
+
+  /// Function that returns the number held by the value, if it is one.
+  pub fn as_number(&self) -> Option<f64> {
+    match self {
+      LoxValue::Number(n) => Some(*n),
+      _ => None,
+    }
+  }
+
+  /// Function that returns the text held by the value, if it is a string.
+  pub fn as_string(&self) -> Option<&str> {
+    match self {
+      LoxValue::String(s) => Some(s),
+      _ => None,
+    }
+  }
+
+  /// Function that returns the boolean held by the value, without applying truthiness.
+  pub fn as_bool(&self) -> Option<bool> {
+    match self {
+      LoxValue::Bool(b) => Some(*b),
+      _ => None,
+    }
+  }
+
+  /// Function that borrows the elements of the value, if it is an array.
+  pub fn as_array(&self) -> Option<Ref<'_, Vec<LoxValue>>> {
+    match self {
+      LoxValue::Array(a) => Some(a.borrow()),
+      _ => None,
+    }
+  }
+
+  /// Function that borrows the entries of the value, if it is a map.
+  pub fn as_map(&self) -> Option<Ref<'_, MapEntries>> {
+    match self {
+      LoxValue::Map(m) => Some(m.borrow()),
+      _ => None,
+    }
+  }
+
+  /// Function that returns the number held by the value, or an error naming `context` (what
+  /// wanted the number) when it isn't one.
+  pub fn expect_number(&self, context: &str) -> Result<f64, InterpreterError> {
+    self.as_number().ok_or_else(|| {
+      InterpreterError::RuntimeError(format!(
+        "{} expects a number, got {}",
+        context,
+        self.to_lox_string()
+      ))
+    })
+  }
+
+  /// Function that formats the value the way `print` shows it to Lox users, numbers print
+  /// without a trailing `.0` and classes and instances by their class name.
+  pub fn to_lox_string(&self) -> String {
+    self.to_lox_string_within(&mut vec![])
+  }
+
+  /// Function that formats the value like [`Self::to_lox_string`], `ancestors` holds the arrays
+  /// and maps being printed so one that holds itself prints as `[...]` or `{...}` instead of
+  /// recursing forever.
+  fn to_lox_string_within(&self, ancestors: &mut Vec<*const ()>) -> String {
+    match self {
+      LoxValue::Nil => "nil".to_string(),
+      LoxValue::Number(n) => format!("{n}"),
+      LoxValue::String(s) => s.clone(),
+      LoxValue::Bool(b) => b.to_string(),
+      LoxValue::Function(_) => "<fn>".to_string(),
+      LoxValue::NativeFunction(_) => "<native fn>".to_string(),
+      LoxValue::Class(c) => c.name.clone(),
+      LoxValue::NativeClass(c) => c.name.to_string(),
+      LoxValue::Instance(i) => format!("{} instance", i.borrow().class.name),
+      LoxValue::Array(a) => {
+        let pointer = Rc::as_ptr(a) as *const ();
+        if ancestors.contains(&pointer) {
+          return "[...]".to_string();
+        }
+
+        ancestors.push(pointer);
+        let elements = a
+          .borrow()
+          .iter()
+          .map(|e| e.to_lox_string_within(ancestors))
+          .collect::<Vec<_>>();
+        ancestors.pop();
+        format!("[{}]", elements.join(", "))
+      },
+      LoxValue::Map(m) => {
+        let pointer = Rc::as_ptr(m) as *const ();
+        if ancestors.contains(&pointer) {
+          return "{...}".to_string();
+        }
+
+        ancestors.push(pointer);
+        let entries = m
+          .borrow()
+          .values()
+          .map(|(k, v)| {
+            format!(
+              "{}: {}",
+              k.to_lox_string_within(ancestors),
+              v.to_lox_string_within(ancestors)
+            )
+          })
+          .collect::<Vec<_>>();
+        ancestors.pop();
+        format!("{{{}}}", entries.join(", "))
+      },
+      LoxValue::WeakRef(_) => "<weakref>".to_string(),
+    }
+  }
+}
+
+impl PartialEq for LoxValue {
+  /// Strings compare by their NFC form, so canonically equivalent spellings are equal while the
+  /// stored text stays as written. Arrays and maps compare by content. Functions, classes and
+  /// instances are never equal.
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (LoxValue::Nil, LoxValue::Nil) => true,
+      (LoxValue::Number(a), LoxValue::Number(b)) => a == b,
+      (LoxValue::String(a), LoxValue::String(b)) => a == b || a.nfc().eq(b.nfc()),
+      (LoxValue::Bool(a), LoxValue::Bool(b)) => a == b,
+      (LoxValue::Array(a), LoxValue::Array(b)) => Rc::ptr_eq(a, b) || *a.borrow() == *b.borrow(),
+      (LoxValue::Map(a), LoxValue::Map(b)) => {
+        Rc::ptr_eq(a, b) || {
+          let (a, b) = (a.borrow(), b.borrow());
+          a.len() == b.len()
+            && a
+              .iter()
+              .all(|(key, (_, value))| b.get(key).is_some_and(|(_, other)| value == other))
+        }
+      },
+      _ => false,
     }
   }
 }
@@ -50,7 +221,52 @@ impl fmt::Display for LoxValue {
       LoxValue::Function(_) => write!(f, "<function>"),
       LoxValue::NativeFunction(_) => write!(f, "<native function>"),
       LoxValue::Class(c) => write!(f, "{c:?}"),
+      LoxValue::NativeClass(c) => write!(f, "{}", c.name),
       LoxValue::Instance(i) => write!(f, "{i:?}"),
+      LoxValue::Array(a) => {
+        let elements = a.borrow().iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        write!(f, "[{}]", elements.join(", "))
+      },
+      LoxValue::Map(m) => {
+        let entries = m
+          .borrow()
+          .values()
+          .map(|(k, v)| format!("{k}: {v}"))
+          .collect::<Vec<_>>();
+        write!(f, "{{{}}}", entries.join(", "))
+      },
+      LoxValue::WeakRef(_) => write!(f, "<weakref>"),
+    }
+  }
+}
+
+impl fmt::Display for InterpreterError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      InterpreterError::Return(value) => write!(f, "return {value}"),
+      InterpreterError::RuntimeError(message) => write!(f, "runtime error: {message}"),
+      InterpreterError::Break => write!(f, "break"),
+      InterpreterError::Continue => write!(f, "continue"),
     }
   }
 }
+
+impl std::error::Error for InterpreterError {}
+
+impl From<io::Error> for InterpreterError {
+  fn from(err: io::Error) -> Self {
+    InterpreterError::RuntimeError(format!("IO error: {err}"))
+  }
+}
+
+impl From<ParseFloatError> for InterpreterError {
+  fn from(err: ParseFloatError) -> Self {
+    InterpreterError::RuntimeError(format!("Invalid literal: {err}"))
+  }
+}
+
+impl From<ParseIntError> for InterpreterError {
+  fn from(err: ParseIntError) -> Self {
+    InterpreterError::RuntimeError(format!("Invalid literal: {err}"))
+  }
+}
@@ -1,14 +1,8 @@
+use std::io::IsTerminal;
+
 use colored::*;
+use compiler::runner::{Runner, STDIN_SOURCE_NAME};
 use diagnostic::{diagnostic::Diagnostic, diagnostic_code::DiagnosticCode, DiagnosticEngine};
-use runner::Runner;
-
-mod class;
-mod env;
-mod error;
-mod function;
-mod interpreter;
-mod lox_value;
-mod runner;
 
 fn main() {
   let args: Vec<String> = std::env::args().collect();
@@ -17,6 +11,15 @@ fn main() {
   let mut compiler = Runner::new();
 
   match args.len() {
+    // Without a script a piped program is run as is, `echo 'print(1);' | compiler`.
+    1 if !std::io::stdin().is_terminal() => {
+      let mut diagnostic = DiagnosticEngine::for_file(STDIN_SOURCE_NAME);
+      compiler.run_stdin(&mut diagnostic);
+
+      if diagnostic.has_errors() {
+        std::process::exit(65);
+      }
+    },
     1 => {
       // Info message for interactive mode
       println!("{}", "Running the interactive mode".cyan().bold());
@@ -25,8 +28,15 @@ fn main() {
     2 => {
       // Info message for file mode
       println!("{}", format!("Running file: {}", args[1]).cyan().bold());
+      let mut diagnostic = DiagnosticEngine::for_file(&args[1]);
+      #[cfg(not(feature = "async"))]
       compiler.run_file(args[1].clone(), &mut diagnostic);
 
+      #[cfg(feature = "async")]
+      tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(compiler.run_file_async(args[1].clone(), &mut diagnostic));
+
       // Check if compilation had errors
       if diagnostic.has_errors() {
         std::process::exit(65);
@@ -0,0 +1,333 @@
+use std::{collections::HashMap, rc::Rc};
+
+use diagnostic::DiagnosticEngine;
+use scanner::token::Token;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+  class::NativeClass,
+  error::type_error,
+  freezable::Freezable,
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+};
+
+/// Hashable form of the values a map accepts as keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MapKey {
+  Nil,
+  Bool(bool),
+  Number(u64),
+  String(String),
+}
+
+impl MapKey {
+  /// Function that returns the key for `value`, `None` for values that can't be keys (arrays,
+  /// maps, functions, classes and instances).
+  pub fn from_value(value: &LoxValue) -> Option<Self> {
+    match value {
+      LoxValue::Nil => Some(MapKey::Nil),
+      LoxValue::Bool(b) => Some(MapKey::Bool(*b)),
+      // `0` and `-0` are equal numbers, so they are the same key.
+      LoxValue::Number(n) if *n == 0.0 => Some(MapKey::Number(0.0_f64.to_bits())),
+      LoxValue::Number(n) => Some(MapKey::Number(n.to_bits())),
+      // Keys hash by the same NFC form strings compare by.
+      LoxValue::String(s) => Some(MapKey::String(s.nfc().collect())),
+      _ => None,
+    }
+  }
+}
+
+/// Key and value of a map entry, as they were written
+pub type MapEntry = (LoxValue, LoxValue);
+
+/// Entries of a map in insertion order, each key keeps the value it was written with next to its
+/// entry
+#[derive(Debug, Clone, Default)]
+pub struct MapEntries {
+  entries: Vec<(MapKey, MapEntry)>,
+  indices: HashMap<MapKey, usize>,
+}
+
+impl MapEntries {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      entries: Vec::with_capacity(capacity),
+      indices: HashMap::with_capacity(capacity),
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn contains_key(&self, key: &MapKey) -> bool {
+    self.indices.contains_key(key)
+  }
+
+  pub fn get(&self, key: &MapKey) -> Option<&MapEntry> {
+    self.indices.get(key).map(|&index| &self.entries[index].1)
+  }
+
+  /// Function that returns the entry of `key`, inserting the result of `default` at the end when
+  /// there is none.
+  pub fn get_or_insert_with(
+    &mut self,
+    key: MapKey,
+    default: impl FnOnce() -> MapEntry,
+  ) -> &mut MapEntry {
+    let index = match self.indices.get(&key) {
+      Some(&index) => index,
+      None => {
+        self.indices.insert(key.clone(), self.entries.len());
+        self.entries.push((key, default()));
+        self.entries.len() - 1
+      },
+    };
+    &mut self.entries[index].1
+  }
+
+  /// Function that sets the entry of `key`, returning the old one. Overwriting a key keeps its
+  /// place, a new key goes last.
+  pub fn insert(&mut self, key: MapKey, entry: MapEntry) -> Option<MapEntry> {
+    match self.indices.get(&key) {
+      Some(&index) => Some(std::mem::replace(&mut self.entries[index].1, entry)),
+      None => {
+        self.indices.insert(key.clone(), self.entries.len());
+        self.entries.push((key, entry));
+        None
+      },
+    }
+  }
+
+  /// Function that removes the entry of `key`, the entries after it keep their order.
+  pub fn remove(&mut self, key: &MapKey) -> Option<MapEntry> {
+    let index = self.indices.remove(key)?;
+    let (_, entry) = self.entries.remove(index);
+    for shifted in self.indices.values_mut() {
+      if *shifted > index {
+        *shifted -= 1;
+      }
+    }
+    Some(entry)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&MapKey, &MapEntry)> {
+    self.entries.iter().map(|(key, entry)| (key, entry))
+  }
+
+  pub fn values(&self) -> impl Iterator<Item = &MapEntry> {
+    self.entries.iter().map(|(_, entry)| entry)
+  }
+}
+
+impl Extend<(MapKey, MapEntry)> for MapEntries {
+  fn extend<I: IntoIterator<Item = (MapKey, MapEntry)>>(&mut self, entries: I) {
+    for (key, entry) in entries {
+      self.insert(key, entry);
+    }
+  }
+}
+
+impl FromIterator<(MapKey, MapEntry)> for MapEntries {
+  fn from_iter<I: IntoIterator<Item = (MapKey, MapEntry)>>(entries: I) -> Self {
+    let mut map = Self::new();
+    map.extend(entries);
+    map
+  }
+}
+
+impl IntoIterator for MapEntries {
+  type Item = (MapKey, MapEntry);
+  type IntoIter = std::vec::IntoIter<(MapKey, MapEntry)>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.entries.into_iter()
+  }
+}
+
+impl<const N: usize> From<[(MapKey, MapEntry); N]> for MapEntries {
+  fn from(entries: [(MapKey, MapEntry); N]) -> Self {
+    entries.into_iter().collect()
+  }
+}
+
+/// Entries of a map value, shared by every variable holding the map
+pub type LoxMap = Rc<Freezable<MapEntries>>;
+
+/// Global `Map` class holding the static map methods
+pub struct MapClass;
+
+impl MapClass {
+  pub fn add(interpreter: &mut Interpreter) {
+    let static_methods = HashMap::from([(
+      "from_entries",
+      LoxValue::NativeFunction(Rc::new(MapFromEntries)),
+    )]);
+
+    interpreter.env.borrow_mut().define(
+      "Map".to_string(),
+      LoxValue::NativeClass(Rc::new(NativeClass {
+        name: "Map",
+        static_methods,
+      })),
+    );
+  }
+}
+
+/// Native method of a map, bound to the map it was read from
+pub struct MapMethod {
+  map: LoxMap,
+  name: &'static str,
+  arity: usize,
+}
+
+impl MapMethod {
+  /// Function that looks up the method `name` on `map`, `None` when maps have no such method.
+  pub fn bind(map: &LoxMap, name: &str) -> Option<LoxValue> {
+    let (name, arity) = match name {
+      "keys" => ("keys", 0),
+      "values" => ("values", 0),
+      "entries" => ("entries", 0),
+      "has" => ("has", 1),
+      "delete" => ("delete", 1),
+      "set" => ("set", 2),
+      "merge" => ("merge", 1),
+      "clone" => ("clone", 0),
+      _ => return None,
+    };
+
+    Some(LoxValue::NativeFunction(Rc::new(MapMethod {
+      map: map.clone(),
+      name,
+      arity,
+    })))
+  }
+}
+
+/// Function that returns the key for `value` or reports why it can't be one.
+pub fn key_of(value: &LoxValue, engine: &mut DiagnosticEngine) -> Result<MapKey, InterpreterError> {
+  MapKey::from_value(value).ok_or_else(|| {
+    type_error(
+      engine,
+      format!("{} can't be used as a map key", value.to_lox_string()),
+    )
+  })
+}
+
+impl LoxCallable for MapMethod {
+  fn arity(&self) -> usize {
+    self.arity
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let map = &self.map;
+
+    // The array-returning methods build new arrays and leave the map untouched.
+    let value = match self.name {
+      "keys" => LoxValue::array(map.borrow().values().map(|(key, _)| key.clone()).collect()),
+      "values" => LoxValue::array(
+        map
+          .borrow()
+          .values()
+          .map(|(_, value)| value.clone())
+          .collect(),
+      ),
+      "entries" => LoxValue::array(
+        map
+          .borrow()
+          .values()
+          .map(|(key, value)| LoxValue::array(vec![key.clone(), value.clone()]))
+          .collect(),
+      ),
+      "has" => LoxValue::Bool(map.borrow().contains_key(&key_of(&arguments[0].0, engine)?)),
+      "delete" => {
+        let key = key_of(&arguments[0].0, engine)?;
+        LoxValue::Bool(map.mutate()?.remove(&key).is_some())
+      },
+      "set" => {
+        let key = key_of(&arguments[0].0, engine)?;
+        map
+          .mutate()?
+          .insert(key, (arguments[0].0.clone(), arguments[1].0.clone()));
+        LoxValue::Nil
+      },
+      "merge" => {
+        let LoxValue::Map(other) = &arguments[0].0 else {
+          return Err(type_error(
+            engine,
+            "merge expects a map argument".to_string(),
+          ));
+        };
+
+        let mut merged = map.borrow().clone();
+        merged.extend(other.borrow().clone());
+        LoxValue::map(merged)
+      },
+      "clone" => LoxValue::map(map.borrow().clone()),
+      _ => unreachable!("unknown map method '{}'", self.name),
+    };
+
+    Ok(value)
+  }
+}
+
+/// `Map.from_entries(entries)`, the inverse of `map.entries()`
+struct MapFromEntries;
+
+impl LoxCallable for MapFromEntries {
+  fn arity(&self) -> usize {
+    1
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let LoxValue::Array(entries) = &arguments[0].0 else {
+      return Err(type_error(
+        engine,
+        "from_entries expects an array of [key, value] pairs".to_string(),
+      ));
+    };
+
+    let mut map = MapEntries::new();
+    for entry in entries.borrow().iter() {
+      let pair = match entry {
+        LoxValue::Array(pair) if pair.borrow().len() == 2 => pair.borrow(),
+        _ => {
+          return Err(type_error(
+            engine,
+            format!(
+              "from_entries expects [key, value] pairs, got {}",
+              entry.to_lox_string()
+            ),
+          ))
+        },
+      };
+
+      map.insert(
+        key_of(&pair[0], engine)?,
+        (pair[0].clone(), pair[1].clone()),
+      );
+    }
+
+    Ok(LoxValue::map(map))
+  }
+}
@@ -0,0 +1,190 @@
+use std::{collections::HashMap, rc::Rc};
+
+use diagnostic::DiagnosticEngine;
+use scanner::token::Token;
+
+use crate::{
+  class::NativeClass,
+  error::type_error,
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+};
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Global `Number` class holding the static number predicates
+pub struct NumberClass;
+
+impl NumberClass {
+  pub fn add(interpreter: &mut Interpreter) {
+    let static_methods = ["is_nan", "is_finite", "is_integer"]
+      .into_iter()
+      .map(|name| {
+        (
+          name,
+          LoxValue::NativeFunction(Rc::new(NumberPredicate { name }) as Rc<dyn LoxCallable>),
+        )
+      })
+      .collect::<HashMap<_, _>>();
+
+    interpreter.env.borrow_mut().define(
+      "Number".to_string(),
+      LoxValue::NativeClass(Rc::new(NativeClass {
+        name: "Number",
+        static_methods,
+      })),
+    );
+  }
+}
+
+/// Native method of a number, bound to the number it was read from
+pub struct NumberMethod {
+  number: f64,
+  name: &'static str,
+  /// Accepted argument counts, the interpreter only checks fixed arities
+  arguments: (usize, usize),
+}
+
+impl NumberMethod {
+  /// Function that looks up the method `name` on `number`, `None` when numbers have no such
+  /// method.
+  pub fn bind(number: f64, name: &str) -> Option<LoxValue> {
+    let (name, arguments) = match name {
+      "to_string" => ("to_string", (0, 1)),
+      _ => return None,
+    };
+
+    Some(LoxValue::NativeFunction(Rc::new(NumberMethod {
+      number,
+      name,
+      arguments,
+    })))
+  }
+
+  /// Function that formats the number in the given radix, radix 10 prints like `print` does.
+  fn to_string(
+    &self,
+    radix: Option<&LoxValue>,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let radix = match radix {
+      None => 10,
+      Some(LoxValue::Number(radix)) if (2.0..=36.0).contains(radix) && radix.fract() == 0.0 => {
+        *radix as u32
+      },
+      Some(LoxValue::Number(radix)) => {
+        return Err(InterpreterError::RuntimeError(format!(
+          "Radix must be between 2 and 36, got {}",
+          radix
+        )))
+      },
+      Some(radix) => {
+        return Err(type_error(
+          engine,
+          format!(
+            "to_string expects a number radix, got {}",
+            radix.to_lox_string()
+          ),
+        ))
+      },
+    };
+
+    if radix == 10 {
+      return Ok(LoxValue::String(
+        LoxValue::Number(self.number).to_lox_string(),
+      ));
+    }
+
+    if self.number.fract() != 0.0 || !self.number.is_finite() {
+      return Err(InterpreterError::RuntimeError(format!(
+        "Only integers can be formatted in radix {}, got {}",
+        radix, self.number
+      )));
+    }
+
+    let mut value = self.number.abs();
+    let mut digits = Vec::new();
+    loop {
+      digits.push(DIGITS[(value % radix as f64) as usize]);
+      value = (value / radix as f64).floor();
+      if value == 0.0 {
+        break;
+      }
+    }
+
+    if self.number < 0.0 {
+      digits.push(b'-');
+    }
+    digits.reverse();
+
+    // Every pushed byte comes from `DIGITS` or is '-', so the digits are ASCII.
+    Ok(LoxValue::String(String::from_utf8(digits).unwrap()))
+  }
+}
+
+impl LoxCallable for NumberMethod {
+  fn arity(&self) -> usize {
+    let (min, max) = self.arguments;
+    if min == max {
+      min
+    } else {
+      usize::MAX
+    }
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let (min, max) = self.arguments;
+    if arguments.len() < min || arguments.len() > max {
+      return Err(InterpreterError::RuntimeError(format!(
+        "{} expects {} to {} arguments but got {}",
+        self.name,
+        min,
+        max,
+        arguments.len()
+      )));
+    }
+
+    match self.name {
+      "to_string" => self.to_string(arguments.first().map(|(value, _)| value), engine),
+      _ => unreachable!("unknown number method '{}'", self.name),
+    }
+  }
+}
+
+/// `Number.is_nan(value)`, `Number.is_finite(value)` and `Number.is_integer(value)`, they are
+/// `false` for values that aren't numbers
+struct NumberPredicate {
+  name: &'static str,
+}
+
+impl LoxCallable for NumberPredicate {
+  fn arity(&self) -> usize {
+    1
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    _engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let LoxValue::Number(n) = arguments[0].0 else {
+      return Ok(LoxValue::Bool(false));
+    };
+
+    let result = match self.name {
+      "is_nan" => n.is_nan(),
+      "is_finite" => n.is_finite(),
+      "is_integer" => n.is_finite() && n.fract() == 0.0,
+      _ => unreachable!("unknown number predicate '{}'", self.name),
+    };
+
+    Ok(LoxValue::Bool(result))
+  }
+}
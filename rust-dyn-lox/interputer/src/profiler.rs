@@ -0,0 +1,35 @@
+use std::{collections::HashMap, time::Duration};
+
+/// Calls made to one callee and the time spent in them.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ProfileEntry {
+  pub calls: usize,
+  pub total: Duration,
+}
+
+/// Call counts and time per callee, kept by an interpreter built with
+/// [`crate::interpreter::InterpreterBuilder::with_profiler`]. Callees are named as they are written
+/// at the call site, so a method shows up under its own name.
+#[derive(Debug, Default, Clone)]
+pub struct Profiler {
+  entries: HashMap<String, ProfileEntry>,
+}
+
+impl Profiler {
+  /// Function that records one call to `name` that took `elapsed`.
+  pub fn record(&mut self, name: &str, elapsed: Duration) {
+    let entry = self.entries.entry(name.to_string()).or_default();
+    entry.calls += 1;
+    entry.total += elapsed;
+  }
+
+  /// Function that returns what was recorded for `name`, `None` when it was never called.
+  pub fn entry(&self, name: &str) -> Option<&ProfileEntry> {
+    self.entries.get(name)
+  }
+
+  /// Function that returns every callee recorded so far.
+  pub fn entries(&self) -> &HashMap<String, ProfileEntry> {
+    &self.entries
+  }
+}
@@ -1,16 +1,34 @@
 use crate::interpreter::Interpreter;
 use diagnostic::{diagnostic::Diagnostic, diagnostic_code::DiagnosticCode, DiagnosticEngine};
-use parser::Parser;
-use scanner::Scanner;
-use semantic_analysis;
+use parser::{stmt::Stmt, Parser};
+use scanner::{token::stream::TokenStream, Scanner};
+use semantic_analysis::resolver::Resolver;
 use std::{
   fs,
-  io::{self, Write},
-  process,
+  io::{self, Read, Write},
+  path::Path,
 };
 
+/// Name diagnostics use for a program piped through stdin.
+pub const STDIN_SOURCE_NAME: &str = "<stdin>";
+
+/// Stage of the pipeline that [`Runner::run_pipeline`] finished without errors.
+#[derive(Debug, Clone, Copy)]
+pub enum Stage<'a> {
+  Scanned,
+  Parsed(&'a [Stmt]),
+  Resolved,
+  Interpreted,
+}
+
 pub struct Runner {}
 
+impl Default for Runner {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl Runner {
   pub fn new() -> Self {
     Self {}
@@ -21,7 +39,7 @@ impl Runner {
     println!("Welcome to DuckLang 🦆");
     println!("Type `exit` to quit.\n");
 
-    let mut interputer = Interpreter::new();
+    let _interputer = Interpreter::new();
 
     loop {
       engine.clear();
@@ -46,31 +64,31 @@ impl Runner {
       }
 
       // Scanning the buffer of string
-      let mut scanner = Scanner::new(input.to_string().clone());
+      let mut scanner = Scanner::new(input);
 
       // Scan the tokens
       scanner.scan(engine);
 
       // Check if there were scanning errors
       if engine.has_errors() {
-        engine.print_all(&input);
+        engine.print_all(input);
         continue;
       }
 
       // Parse the tokens
-      let mut parser = Parser::new(scanner.tokens);
+      let mut parser = Parser::new(TokenStream::new(scanner.tokens));
       parser.parse(engine);
 
       // Check if there were parsing errors
       if engine.has_errors() {
-        engine.print_all(&input);
+        engine.print_all(input);
         continue;
       }
 
       // interputer.run(parser.ast, engine);
 
       if engine.has_errors() {
-        engine.print_all(&input);
+        engine.print_all(input);
         continue;
       }
 
@@ -78,81 +96,166 @@ impl Runner {
     }
   }
 
+  /// Function that reads the whole source file at `path`.
+  pub fn read_file(path: impl AsRef<Path>) -> io::Result<String> {
+    fs::read_to_string(path)
+  }
+
+  /// Function that reads the whole program piped through stdin.
+  pub fn read_stdin() -> io::Result<String> {
+    let mut source = String::new();
+    io::stdin().read_to_string(&mut source)?;
+    Ok(source)
+  }
+
+  /// Function that reads the whole source file at `path` without blocking the async runtime.
+  #[cfg(feature = "async")]
+  pub async fn read_file_async(path: impl AsRef<Path>) -> io::Result<String> {
+    tokio::fs::read_to_string(path).await
+  }
+
   /// Function that runs the process of compiling file.
   pub fn run_file(&mut self, path: String, engine: &mut DiagnosticEngine) {
-    // Reading files to get the string buff
-    let source = match fs::read_to_string(&path) {
-      Ok(content) => content,
-      Err(err) => {
-        let diagnostic = Diagnostic::new(
-          DiagnosticCode::FileNotFound,
-          format!("could not read file: {}", path),
-        )
-        .with_help(format!("reason: {}", err));
-
-        engine.emit(diagnostic);
-        engine.print_all("");
-        std::process::exit(66);
-      },
-    };
+    match Self::read_file(&path) {
+      Ok(source) => self.inturpret(source, engine),
+      Err(err) => Self::exit_unreadable(&path, err, engine),
+    }
+  }
+
+  /// Function that runs the process of compiling the program piped through stdin.
+  pub fn run_stdin(&mut self, engine: &mut DiagnosticEngine) {
+    match Self::read_stdin() {
+      Ok(source) => self.inturpret(source, engine),
+      Err(err) => Self::exit_unreadable(STDIN_SOURCE_NAME, err, engine),
+    }
+  }
+
+  /// Function that runs the process of compiling file, awaiting the read on the async runtime.
+  #[cfg(feature = "async")]
+  pub async fn run_file_async(&mut self, path: String, engine: &mut DiagnosticEngine) {
+    match Self::read_file_async(&path).await {
+      Ok(source) => self.inturpret(source, engine),
+      Err(err) => Self::exit_unreadable(&path, err, engine),
+    }
+  }
 
-    self.inturpret(source, engine);
+  /// Function that reports a file that could not be read and exits with `EX_NOINPUT`.
+  fn exit_unreadable(path: &str, err: io::Error, engine: &mut DiagnosticEngine) -> ! {
+    let diagnostic = Diagnostic::new(
+      DiagnosticCode::FileNotFound,
+      format!("could not read file: {}", path),
+    )
+    .with_help(format!("reason: {}", err));
+
+    engine.emit(diagnostic);
+    engine.print_all("");
+    std::process::exit(66);
   }
 
   pub fn inturpret(&mut self, source: String, engine: &mut DiagnosticEngine) {
+    self.inturpret_with(source, &mut Interpreter::new(), engine);
+  }
+
+  /// Function that runs `source` through scanning, parsing, resolving and interpreting on its own
+  /// engine without printing anything, and hands back every diagnostic it emitted once any of
+  /// them is an error so embedders decide how to present them. `source_name` is the file the
+  /// diagnostics point into.
+  pub fn run(&mut self, source: &str, source_name: Option<&str>) -> Result<(), Vec<Diagnostic>> {
+    let mut engine = match source_name {
+      Some(name) => DiagnosticEngine::for_file(name),
+      None => DiagnosticEngine::new(),
+    };
+    self.run_pipeline(source, &mut Interpreter::new(), &mut engine, |_| {});
+
+    if engine.has_errors() {
+      return Err(engine.into_diagnostics());
+    }
+    Ok(())
+  }
+
+  /// Function that runs `source` through every stage into `interputer` and prints the banner of
+  /// each stage that passed, or every diagnostic once one of them failed.
+  pub fn inturpret_with(
+    &mut self,
+    source: String,
+    interputer: &mut Interpreter,
+    engine: &mut DiagnosticEngine,
+  ) {
     println!("\n============== READ =================\n");
     println!("{}", source);
 
-    // Scanning the buffer of string
-    let mut scanner = Scanner::new(source.clone());
+    let ran = self.run_pipeline(&source, interputer, engine, |stage| match stage {
+      Stage::Scanned => {
+        println!("\n============= SCANNED ===============\n");
+        // println!("ToLongVector(value...) {:#?}", scanner.tokens);
+        println!("ToLongVector(value..)\n");
+      },
+      Stage::Parsed(ast) => {
+        for stmt in ast {
+          stmt.print_tree();
+        }
 
-    // Scan the tokens
-    scanner.scan(engine);
+        println!("\n============== PARSED ===============\n");
+        println!("ToLongTree(value..)");
 
-    // Check if there were scanning errors
-    if engine.has_errors() {
+        println!("\n======== SEMANTIC ANALYSIS ==========\n");
+      },
+      Stage::Resolved => {},
+      Stage::Interpreted => println!("\n============ INTERPRETED ============\n"),
+    });
+
+    if !ran {
       engine.print_all(&source);
       return;
     }
 
-    println!("\n============= SCANNED ===============\n");
-    // println!("ToLongVector(value...) {:#?}", scanner.tokens);
-    println!("ToLongVector(value..)\n");
-
-    // Parse the tokens
-    let mut parser = Parser::new(scanner.tokens);
-    parser.parse(engine);
-
-    // Check if there were parsing errors
-    if engine.has_errors() {
+    // Warnings don't stop any stage, so they are only shown once the program ran.
+    if engine.warning_count() > 0 {
       engine.print_all(&source);
-      return;
     }
 
-    println!("\n============== PARSED ===============\n");
-    println!("ToLongTree(value..)");
+    // If no errors, compilation succeeded
+    println!("Compilation successful!");
+  }
+
+  /// Function that runs `source` through every stage into `interputer` without printing anything,
+  /// a stage only runs when the ones before it reported no errors. `on_stage` is called after each
+  /// stage that passed, and `true` comes back once the program ran.
+  pub fn run_pipeline(
+    &mut self,
+    source: &str,
+    interputer: &mut Interpreter,
+    engine: &mut DiagnosticEngine,
+    mut on_stage: impl FnMut(Stage<'_>),
+  ) -> bool {
+    let mut scanner = Scanner::new(source);
+    scanner.scan(engine);
+    if !engine.proceed() {
+      return false;
+    }
+    on_stage(Stage::Scanned);
 
-    println!("\n======== SEMANTIC ANALYSIS ==========\n");
+    let mut parser = Parser::new(TokenStream::new(scanner.tokens));
+    parser.parse(engine);
+    if !engine.proceed() {
+      return false;
+    }
+    on_stage(Stage::Parsed(&parser.ast));
 
-    let mut resolver = semantic_analysis::resolver::Resolver::new();
+    let mut resolver = Resolver::new();
     resolver.run(&parser.ast, engine);
-
-    // engine.print_all(&source);
-    if engine.has_errors() {
-      return;
+    if !engine.proceed() {
+      return false;
     }
+    on_stage(Stage::Resolved);
 
     let locals = resolver.get_locals().clone();
-    let mut interputer = Interpreter::new();
     interputer.run(parser.ast, locals, engine);
-
-    if engine.has_errors() {
-      engine.print_all(&source);
-      return;
+    if !engine.proceed() {
+      return false;
     }
-    println!("\n============ INTERPRETED ============\n");
+    on_stage(Stage::Interpreted);
 
-    // If no errors, compilation succeeded
-    println!("Compilation successful!");
+    true
   }
 }
@@ -0,0 +1,218 @@
+use std::{collections::HashMap, rc::Rc};
+
+use diagnostic::DiagnosticEngine;
+use scanner::token::Token;
+
+use crate::{
+  class::NativeClass,
+  error::type_error,
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+};
+
+/// Global `String` class holding the static string methods
+pub struct StringClass;
+
+impl StringClass {
+  pub fn add(interpreter: &mut Interpreter) {
+    let static_methods = HashMap::from([(
+      "from_code_point",
+      LoxValue::NativeFunction(Rc::new(StringFromCodePoint)),
+    )]);
+
+    interpreter.env.borrow_mut().define(
+      "String".to_string(),
+      LoxValue::NativeClass(Rc::new(NativeClass {
+        name: "String",
+        static_methods,
+      })),
+    );
+  }
+}
+
+/// Native method of a string, bound to the string it was read from
+pub struct StringMethod {
+  string: String,
+  name: &'static str,
+  /// Accepted argument counts, the interpreter only checks fixed arities
+  arguments: (usize, usize),
+}
+
+impl StringMethod {
+  /// Function that looks up the method `name` on `string`, `None` when strings have no such
+  /// method.
+  pub fn bind(string: &str, name: &str) -> Option<LoxValue> {
+    let (name, arguments) = match name {
+      "pad_start" => ("pad_start", (1, 2)),
+      "pad_end" => ("pad_end", (1, 2)),
+      "code_point_at" => ("code_point_at", (1, 1)),
+      _ => return None,
+    };
+
+    Some(LoxValue::NativeFunction(Rc::new(StringMethod {
+      string: string.to_string(),
+      name,
+      arguments,
+    })))
+  }
+
+  /// Function that pads the string with `fill` up to `length` chars, at the start or the end.
+  fn pad(
+    &self,
+    arguments: &[LoxValue],
+    at_start: bool,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let length = match arguments[0] {
+      LoxValue::Number(length) if length >= 0.0 => length as usize,
+      _ => {
+        return Err(type_error(
+          engine,
+          format!(
+            "{} expects a non-negative length, got {}",
+            self.name,
+            arguments[0].to_lox_string()
+          ),
+        ))
+      },
+    };
+
+    let fill = match arguments.get(1) {
+      None => ' ',
+      Some(LoxValue::String(fill)) if fill.chars().count() == 1 => fill.chars().next().unwrap(),
+      Some(LoxValue::String(_)) => {
+        return Err(InterpreterError::RuntimeError(
+          "Padding character must be a single character.".to_string(),
+        ))
+      },
+      Some(fill) => {
+        return Err(type_error(
+          engine,
+          format!(
+            "{} expects a string to pad with, got {}",
+            self.name,
+            fill.to_lox_string()
+          ),
+        ))
+      },
+    };
+
+    let padding = fill
+      .to_string()
+      .repeat(length.saturating_sub(self.string.chars().count()));
+
+    Ok(LoxValue::String(if at_start {
+      padding + &self.string
+    } else {
+      self.string.clone() + &padding
+    }))
+  }
+
+  /// Function that returns the code point of the char at `index`, `nil` when there is no char
+  /// there.
+  fn code_point_at(
+    &self,
+    index: &LoxValue,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let LoxValue::Number(index) = index else {
+      return Err(type_error(
+        engine,
+        format!(
+          "code_point_at expects a number index, got {}",
+          index.to_lox_string()
+        ),
+      ));
+    };
+
+    if *index < 0.0 || index.fract() != 0.0 {
+      return Ok(LoxValue::Nil);
+    }
+
+    Ok(
+      self
+        .string
+        .chars()
+        .nth(*index as usize)
+        .map_or(LoxValue::Nil, |c| LoxValue::Number(c as u32 as f64)),
+    )
+  }
+}
+
+impl LoxCallable for StringMethod {
+  fn arity(&self) -> usize {
+    let (min, max) = self.arguments;
+    if min == max {
+      min
+    } else {
+      usize::MAX
+    }
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let (min, max) = self.arguments;
+    if arguments.len() < min || arguments.len() > max {
+      return Err(InterpreterError::RuntimeError(format!(
+        "{} expects {} to {} arguments but got {}",
+        self.name,
+        min,
+        max,
+        arguments.len()
+      )));
+    }
+
+    let arguments = arguments
+      .into_iter()
+      .map(|(value, _)| value)
+      .collect::<Vec<_>>();
+
+    match self.name {
+      "pad_start" => self.pad(&arguments, true, engine),
+      "pad_end" => self.pad(&arguments, false, engine),
+      "code_point_at" => self.code_point_at(&arguments[0], engine),
+      _ => unreachable!("unknown string method '{}'", self.name),
+    }
+  }
+}
+
+/// `String.from_code_point(n)`, the inverse of `string.code_point_at(0)`
+struct StringFromCodePoint;
+
+impl LoxCallable for StringFromCodePoint {
+  fn arity(&self) -> usize {
+    1
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    let LoxValue::Number(code_point) = arguments[0].0 else {
+      return Err(type_error(
+        engine,
+        format!(
+          "from_code_point expects a number, got {}",
+          arguments[0].0.to_lox_string()
+        ),
+      ));
+    };
+
+    // `char::from_u32` rejects the surrogate range and anything past 0x10FFFF.
+    let c = (code_point >= 0.0 && code_point.fract() == 0.0 && code_point <= u32::MAX as f64)
+      .then(|| char::from_u32(code_point as u32))
+      .flatten()
+      .ok_or_else(|| {
+        InterpreterError::RuntimeError(format!("Invalid code point: {}", code_point))
+      })?;
+
+    Ok(LoxValue::String(c.to_string()))
+  }
+}
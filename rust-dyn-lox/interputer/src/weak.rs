@@ -0,0 +1,122 @@
+use std::{
+  cell::RefCell,
+  rc::{Rc, Weak},
+};
+
+use diagnostic::DiagnosticEngine;
+use scanner::token::Token;
+
+use crate::{
+  class::LoxClassInstance,
+  function::LoxCallable,
+  interpreter::Interpreter,
+  lox_value::{InterpreterError, LoxValue},
+};
+
+/// Reference to a value that doesn't keep the value alive, it dies with the last strong reference
+#[derive(Debug, Clone)]
+pub enum WeakLoxValue {
+  Instance(Weak<RefCell<LoxClassInstance>>),
+}
+
+impl WeakLoxValue {
+  /// Function that returns the referenced value, `None` once it was dropped.
+  pub fn upgrade(&self) -> Option<LoxValue> {
+    match self {
+      WeakLoxValue::Instance(instance) => instance.upgrade().map(LoxValue::Instance),
+    }
+  }
+}
+
+/// Weakref native function, `weakref(instance)` returns a weak reference to the instance
+pub struct WeakRefFunction;
+
+/// Is dead native function, `is_dead(weakref)` is `true` once the referenced value was dropped
+pub struct IsDeadFunction;
+
+impl WeakRefFunction {
+  pub fn add(interpreter: &mut Interpreter) {
+    let mut env = interpreter.env.borrow_mut();
+    env.define(
+      "weakref".to_string(),
+      LoxValue::NativeFunction(Rc::new(WeakRefFunction)),
+    );
+    env.define(
+      "is_dead".to_string(),
+      LoxValue::NativeFunction(Rc::new(IsDeadFunction)),
+    );
+  }
+}
+
+impl LoxCallable for WeakRefFunction {
+  fn arity(&self) -> usize {
+    1
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    _engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    match &arguments[0].0 {
+      LoxValue::Instance(instance) => Ok(LoxValue::WeakRef(WeakLoxValue::Instance(Rc::downgrade(
+        instance,
+      )))),
+      value => Err(InterpreterError::RuntimeError(format!(
+        "weakref expects an instance, got {}",
+        value.to_lox_string()
+      ))),
+    }
+  }
+}
+
+impl LoxCallable for IsDeadFunction {
+  fn arity(&self) -> usize {
+    1
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    arguments: Vec<(LoxValue, Option<Token>)>,
+    _engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    match &arguments[0].0 {
+      LoxValue::WeakRef(weak) => Ok(LoxValue::Bool(weak.upgrade().is_none())),
+      value => Err(InterpreterError::RuntimeError(format!(
+        "is_dead expects a weakref, got {}",
+        value.to_lox_string()
+      ))),
+    }
+  }
+}
+
+/// Native method of a weak reference, bound to the reference it was read from
+pub struct WeakRefMethod {
+  weak: WeakLoxValue,
+}
+
+impl WeakRefMethod {
+  /// Function that looks up the method `name` on `weak`, `None` when weak references have no
+  /// such method.
+  pub fn bind(weak: &WeakLoxValue, name: &str) -> Option<LoxValue> {
+    (name == "deref")
+      .then(|| LoxValue::NativeFunction(Rc::new(WeakRefMethod { weak: weak.clone() })))
+  }
+}
+
+impl LoxCallable for WeakRefMethod {
+  fn arity(&self) -> usize {
+    0
+  }
+
+  fn call(
+    &self,
+    _interpreter: &mut Interpreter,
+    _arguments: Vec<(LoxValue, Option<Token>)>,
+    _engine: &mut DiagnosticEngine,
+  ) -> Result<LoxValue, InterpreterError> {
+    Ok(self.weak.upgrade().unwrap_or(LoxValue::Nil))
+  }
+}
@@ -0,0 +1,264 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use compiler::lox_value::LoxValue;
+  use diagnostic::diagnostic_code::DiagnosticCode;
+
+  use crate::common::{global, interpret};
+
+  #[test]
+  fn test_array_literals() {
+    let (interpreter, engine) = interpret("var empty = []; var mixed = [1, \"two\", [nil]];");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "empty"), "[]");
+    assert_eq!(global(&interpreter, "mixed"), "[1, two, [nil]]");
+  }
+
+  #[test]
+  fn test_zip_same_length() {
+    let (interpreter, engine) = interpret("var z = [1, 2, 3].zip([4, 5, 6]);");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "z"), "[[1, 4], [2, 5], [3, 6]]");
+  }
+
+  #[test]
+  fn test_zip_stops_at_the_shorter_array() {
+    let (interpreter, engine) = interpret("var a = [1, 2, 3].zip([4]); var b = [1].zip([4, 5]);");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "[[1, 4]]");
+    assert_eq!(global(&interpreter, "b"), "[[1, 4]]");
+  }
+
+  #[test]
+  fn test_unzip_restores_the_zipped_arrays() {
+    let (interpreter, engine) = interpret(
+      "var a = [1, 2, 3]; var b = [\"x\", \"y\", \"z\"];
+      var parts = a.zip(b).unzip();
+      var same = parts == [a, b];",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "parts"), "[[1, 2, 3], [x, y, z]]");
+    assert!(matches!(
+      interpreter.env.borrow().get("same"),
+      Some(LoxValue::Bool(true))
+    ));
+  }
+
+  #[test]
+  fn test_unzip_of_non_pairs_is_a_type_error() {
+    for source in ["var u = [1, 2].unzip();", "var u = [[1, 2], [3]].unzip();"] {
+      let (interpreter, engine) = interpret(source);
+
+      assert_eq!(
+        engine.get_diagnostics()[0].code,
+        DiagnosticCode::TypeError,
+        "{}",
+        source
+      );
+      assert!(interpreter.env.borrow().get("u").is_none());
+    }
+  }
+
+  #[test]
+  fn test_flat_map_concatenates_results_of_any_length() {
+    let (interpreter, engine) = interpret(
+      "fun repeat(n) {
+        var out = [];
+        if (n == 1) out = [1];
+        if (n == 2) out = [2, 2];
+        return out;
+      }
+      var flat = [0, 1, 2].flat_map(repeat);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "flat"), "[1, 2, 2]");
+  }
+
+  #[test]
+  fn test_flat_map_needs_an_array_result() {
+    let (_, engine) = interpret("fun id(x) { return x; } var flat = [1].flat_map(id);");
+
+    assert_eq!(engine.get_diagnostics()[0].code, DiagnosticCode::TypeError);
+  }
+
+  #[test]
+  fn test_flatten_by_depth() {
+    let (interpreter, engine) = interpret(
+      "var nested = [[1, 2], [3, [4]]];
+      var zero = nested.flatten(0);
+      var one = nested.flatten(1);
+      var two = nested.flatten(2);
+      var all = [1, [2, [3, [4, [5]]]]].flatten(1e9);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "zero"), "[[1, 2], [3, [4]]]");
+    assert_eq!(global(&interpreter, "one"), "[1, 2, 3, [4]]");
+    assert_eq!(global(&interpreter, "two"), "[1, 2, 3, 4]");
+    assert_eq!(global(&interpreter, "all"), "[1, 2, 3, 4, 5]");
+  }
+
+  #[test]
+  fn test_flatten_depth_must_be_a_non_negative_integer() {
+    for depth in ["-1", "1.5", "1e400", "0 % 0", "\"1\""] {
+      let (_, engine) = interpret(&format!("var flat = [[1]].flatten({});", depth));
+
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::TypeError, "{}", depth);
+      assert!(diagnostic
+        .message
+        .starts_with("flatten expects a non-negative integer depth"));
+    }
+  }
+
+  #[test]
+  fn test_flatten_of_array_holding_itself_is_an_error() {
+    let (interpreter, engine) = interpret("var a = [1]; a.push(a); var flat = a.flatten(1e9);");
+
+    let diagnostic = &engine.get_diagnostics()[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::RuntimeError);
+    assert_eq!(diagnostic.message, "Cannot flatten circular structure.");
+    assert!(interpreter.env.borrow().get("flat").is_none());
+
+    // At depth 0 nothing is unwrapped, so the cycle is never reached.
+    let (_, engine) = interpret("var a = [1]; a.push(a); var flat = a.flatten(0);");
+    assert!(!engine.has_errors());
+  }
+
+  #[test]
+  fn test_unique_keeps_the_first_of_each_value() {
+    let (interpreter, engine) = interpret(
+      "var a = [1, 2, 1, 3, 2].unique(); var empty = [].unique();
+      var same = [7, 7, 7].unique();",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "[1, 2, 3]");
+    assert_eq!(global(&interpreter, "empty"), "[]");
+    assert_eq!(global(&interpreter, "same"), "[7]");
+  }
+
+  #[test]
+  fn test_unique_of_mixed_types() {
+    let (interpreter, engine) =
+      interpret("var a = [1, \"1\", nil, true, 1, nil, \"1\", false, [1], [1], [2]].unique();");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      global(&interpreter, "a"),
+      "[1, 1, nil, true, false, [1], [2]]"
+    );
+  }
+
+  #[test]
+  fn test_unique_of_instances() {
+    // Instances are never equal, so every one of them is kept.
+    let (interpreter, engine) = interpret(
+      "class Point {} var p = Point(); var q = Point();
+      var a = [p, 1, q, 1, p].unique();",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      global(&interpreter, "a"),
+      "[Point instance, 1, Point instance, Point instance]"
+    );
+  }
+
+  #[test]
+  fn test_chunk() {
+    let (interpreter, engine) = interpret(
+      "var exact = [1, 2, 3, 4].chunk(2); var rest = [1, 2, 3, 4, 5].chunk(2);
+      var one = [1, 2].chunk(5); var singles = [1, 2, 3].chunk(1); var empty = [].chunk(3);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "exact"), "[[1, 2], [3, 4]]");
+    assert_eq!(global(&interpreter, "rest"), "[[1, 2], [3, 4], [5]]");
+    assert_eq!(global(&interpreter, "one"), "[[1, 2]]");
+    assert_eq!(global(&interpreter, "singles"), "[[1], [2], [3]]");
+    assert_eq!(global(&interpreter, "empty"), "[]");
+  }
+
+  #[test]
+  fn test_chunk_size_must_be_a_positive_integer() {
+    for size in ["0", "-2", "1.5", "\"2\""] {
+      let (_, engine) = interpret(&format!("[1, 2, 3].chunk({});", size));
+
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::RuntimeError);
+      assert!(diagnostic
+        .message
+        .starts_with("Chunk size must be a positive integer"));
+    }
+  }
+
+  #[test]
+  fn test_push_and_pop() {
+    let (interpreter, engine) = interpret(
+      "var a = [1]; a.push(2); a.push(3); var last = a.pop();
+      var empty = []; var none = empty.pop();",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "[1, 2]");
+    assert_eq!(global(&interpreter, "last"), "3");
+    assert_eq!(global(&interpreter, "none"), "nil");
+  }
+
+  #[test]
+  fn test_array_holding_itself_prints() {
+    let (interpreter, engine) =
+      interpret("var a = [1]; a.push(a); print(a); var b = [2]; var c = [b, b];");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "[1, [...]]");
+    // An array seen twice without holding itself prints in full both times.
+    assert_eq!(global(&interpreter, "c"), "[[2], [2]]");
+  }
+
+  #[test]
+  fn test_frozen_array_rejects_push() {
+    for mutation in ["a.push(4);", "a.pop();"] {
+      let (interpreter, engine) = interpret(&format!("var a = freeze([1, 2, 3]); {}", mutation));
+
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::RuntimeError);
+      assert_eq!(diagnostic.message, "Cannot mutate frozen object.");
+      assert_eq!(global(&interpreter, "a"), "[1, 2, 3]");
+    }
+  }
+
+  #[test]
+  fn test_frozen_array_can_be_read() {
+    let (interpreter, engine) = interpret(
+      "var a = [[1], [2]]; freeze(a); var frozen = is_frozen(a);
+      var chunks = a.flatten(1).chunk(1); var copy = deepcopy(a); copy.push(3);
+      var copy_frozen = is_frozen(copy);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "frozen"), "true");
+    assert_eq!(global(&interpreter, "chunks"), "[[1], [2]]");
+    assert_eq!(global(&interpreter, "copy"), "[[1], [2], 3]");
+    assert_eq!(global(&interpreter, "copy_frozen"), "false");
+  }
+
+  #[test]
+  fn test_freeze_is_shallow() {
+    let (interpreter, engine) = interpret(
+      "var inner = [1]; var outer = freeze([inner]); inner.push(2);
+      var inner_frozen = is_frozen(inner);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "outer"), "[[1, 2]]");
+    assert_eq!(global(&interpreter, "inner_frozen"), "false");
+  }
+}
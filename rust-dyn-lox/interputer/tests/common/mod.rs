@@ -0,0 +1,46 @@
+// Every test crate pulls in the whole fixture but only calls part of it.
+#![allow(dead_code)]
+
+use compiler::interpreter::Interpreter;
+use diagnostic::DiagnosticEngine;
+use parser::Parser;
+use scanner::Scanner;
+use semantic_analysis::resolver::Resolver;
+
+/// Runs `source` through scanning, parsing, resolving and interpreting on a fresh interpreter.
+pub fn interpret(source: &str) -> (Interpreter, DiagnosticEngine) {
+  interpret_with(Interpreter::new(), source)
+}
+
+/// Runs `source` through every stage into `interpreter`, which the caller may have built with
+/// its own options.
+pub fn interpret_with(
+  mut interpreter: Interpreter,
+  source: &str,
+) -> (Interpreter, DiagnosticEngine) {
+  let mut engine = DiagnosticEngine::new();
+  let (tokens, warnings) = Scanner::new(source.to_string()).scan_tokens().unwrap();
+  for warning in warnings {
+    engine.emit(warning);
+  }
+
+  let mut parser = Parser::new(tokens);
+  parser.parse(&mut engine);
+
+  let mut resolver = Resolver::new();
+  resolver.run(&parser.ast, &mut engine);
+
+  let locals = resolver.get_locals().clone();
+  interpreter.run(parser.ast, locals, &mut engine);
+  (interpreter, engine)
+}
+
+/// Returns the global `name` printed the way `print` shows it.
+pub fn global(interpreter: &Interpreter, name: &str) -> String {
+  interpreter
+    .env
+    .borrow()
+    .get(name)
+    .map(|value| value.to_lox_string())
+    .unwrap_or_else(|| panic!("no global '{}'", name))
+}
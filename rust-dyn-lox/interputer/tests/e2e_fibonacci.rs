@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+  use std::{
+    fs,
+    process::Command,
+    time::{Duration, Instant},
+  };
+
+  const FIB: &str = "fun fib(n) { if (n <= 1) return n; return fib(n-2) + fib(n-1); }";
+
+  /// Runs `source` through the compiler binary and returns its stdout.
+  fn run(name: &str, source: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{}-{}.duck", name, std::process::id()));
+    fs::write(&path, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_compiler"))
+      .arg(&path)
+      .output()
+      .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "compiler failed:\n{}", stdout);
+    stdout
+  }
+
+  #[test]
+  fn test_recursive_fibonacci() {
+    let stdout = run("e2e_fib", &format!("{}\nprint(fib(10));\n", FIB));
+
+    assert!(stdout.lines().any(|line| line == "55"), "{}", stdout);
+  }
+
+  /// Guards against catastrophic slowdowns, runs with `cargo test --release` since a debug build
+  /// of the tree-walker is several times slower than the budget.
+  #[test]
+  #[cfg_attr(debug_assertions, ignore)]
+  fn test_recursive_fibonacci_performance() {
+    let start = Instant::now();
+    let stdout = run("e2e_fib_30", &format!("{}\nprint(fib(30));\n", FIB));
+
+    assert!(stdout.lines().any(|line| line == "832040"), "{}", stdout);
+    assert!(start.elapsed() < Duration::from_secs(5));
+  }
+}
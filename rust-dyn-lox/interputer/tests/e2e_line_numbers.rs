@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+  use std::{fs, process::Command};
+
+  /// Runs `source` through the compiler binary and returns every reported `file:line:column`.
+  fn error_locations(name: &str, source: &str) -> Vec<String> {
+    let path = std::env::temp_dir().join(format!("{}-{}.duck", name, std::process::id()));
+    fs::write(&path, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_compiler"))
+      .arg(&path)
+      .env("NO_COLOR", "1")
+      .output()
+      .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    // Locations are printed as `  --> file:line:column`.
+    String::from_utf8(output.stdout)
+      .unwrap()
+      .lines()
+      .filter_map(|line| line.trim().strip_prefix("--> "))
+      .map(|location| location.to_string())
+      .collect()
+  }
+
+  /// Runs `source` through the compiler binary and returns the line of every reported diagnostic.
+  fn error_lines(name: &str, source: &str) -> Vec<usize> {
+    error_locations(name, source)
+      .iter()
+      .map(|location| location.rsplit(':').nth(1).unwrap().parse().unwrap())
+      .collect()
+  }
+
+  #[test]
+  fn test_parse_error_on_first_line() {
+    assert_eq!(error_lines("lines_first", "var a = 1; var = 2;\n"), vec![1]);
+  }
+
+  #[test]
+  fn test_parse_error_on_second_statement_of_a_line() {
+    let source = "var a = 1; var b = 2;\nvar c = 3; var = 4;\n";
+
+    assert_eq!(error_lines("lines_parse", source), vec![2]);
+  }
+
+  #[test]
+  fn test_runtime_error_on_second_statement_of_a_line() {
+    let source = "var a = 1; var b = \"x\";\nprint(a); var c = a - b;\n";
+
+    assert_eq!(error_lines("lines_runtime", source), vec![2]);
+  }
+
+  #[test]
+  fn test_error_after_multi_line_string() {
+    let source = "var a = `one\ntwo`; var b = 1;\nb = b - a;\n";
+
+    assert_eq!(error_lines("lines_string", source), vec![3]);
+  }
+
+  #[test]
+  fn test_error_after_line_comment() {
+    let source = "// leading comment\nvar a = 1; var = 2;\n";
+
+    assert_eq!(error_lines("lines_comment", source), vec![2]);
+  }
+
+  #[test]
+  fn test_errors_name_the_source_file() {
+    let locations = error_locations("lines_file", "var = 2;\n");
+    let path = std::env::temp_dir().join(format!("lines_file-{}.duck", std::process::id()));
+
+    assert_eq!(locations.len(), 1);
+    assert!(locations[0].starts_with(&format!("{}:1:", path.display())));
+  }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+  use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+  };
+
+  /// Pipes `source` into the compiler binary started without a script.
+  fn run_piped(source: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_compiler"))
+      .env("NO_COLOR", "1")
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .spawn()
+      .unwrap();
+
+    child
+      .stdin
+      .take()
+      .unwrap()
+      .write_all(source.as_bytes())
+      .unwrap();
+    child.wait_with_output().unwrap()
+  }
+
+  #[test]
+  fn test_piped_program_runs() {
+    let output = run_piped("print(1 + 1);\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.lines().any(|line| line == "2"), "{}", stdout);
+  }
+
+  #[test]
+  fn test_piped_program_errors_name_stdin() {
+    let output = run_piped("var = 2;\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(output.status.code(), Some(65));
+    assert!(
+      stdout
+        .lines()
+        .any(|line| line.trim().starts_with("--> <stdin>:1:")),
+      "{}",
+      stdout
+    );
+  }
+}
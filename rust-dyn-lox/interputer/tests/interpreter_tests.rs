@@ -0,0 +1,656 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::RefCell, rc::Rc};
+
+  use compiler::{
+    function::LoxCallable,
+    import::ImportResolver,
+    interpreter::{Interpreter, InterpreterBuilder, InterpreterConfig},
+    lox_value::{InterpreterError, LoxValue},
+  };
+  use diagnostic::{diagnostic_code::DiagnosticCode, DiagnosticEngine};
+  use scanner::token::Token;
+
+  use crate::common::{interpret, interpret_with};
+
+  fn global(interpreter: &Interpreter, name: &str) -> Option<LoxValue> {
+    interpreter.env.borrow().get(name)
+  }
+
+  #[test]
+  fn test_for_loop_without_any_clause() {
+    let (_, engine) = interpret("for (;;) { break; }");
+
+    assert!(!engine.has_errors());
+  }
+
+  #[test]
+  fn test_for_loop_with_only_an_initializer() {
+    let (interpreter, engine) =
+      interpret("var n = 0; for (var i = 0;;) { i = i + 1; n = n + 1; if (i >= 3) break; }");
+
+    assert!(!engine.has_errors());
+    assert!(matches!(global(&interpreter, "n"), Some(LoxValue::Number(n)) if n == 3.0));
+  }
+
+  #[test]
+  fn test_builder_applies_output_and_globals() {
+    let output = Rc::new(RefCell::new(Vec::<u8>::new()));
+    let interpreter = InterpreterBuilder::default()
+      .with_output(output.clone())
+      .with_global("answer", LoxValue::Number(42.0))
+      .with_global("name", LoxValue::String("duck".to_string()))
+      .build();
+
+    let (interpreter, engine) = interpret_with(interpreter, "print(name, answer);");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      String::from_utf8(output.borrow().clone()).unwrap(),
+      "duck 42\n"
+    );
+    assert!(matches!(global(&interpreter, "answer"), Some(LoxValue::Number(n)) if n == 42.0));
+  }
+
+  /// Import resolver knowing a single module.
+  struct OneModule;
+
+  impl ImportResolver for OneModule {
+    fn resolve(&self, path: &str) -> Result<String, InterpreterError> {
+      match path {
+        "math" => Ok("var pi = 3.14;".to_string()),
+        _ => Err(InterpreterError::RuntimeError(format!(
+          "No module '{}'",
+          path
+        ))),
+      }
+    }
+  }
+
+  #[test]
+  fn test_builder_applies_every_option_together() {
+    let output = Rc::new(RefCell::new(Vec::<u8>::new()));
+    let error_output = Rc::new(RefCell::new(Vec::<u8>::new()));
+    let interpreter = InterpreterBuilder::default()
+      .with_output(output.clone())
+      .with_error_output(error_output.clone())
+      .with_import_resolver(Rc::new(OneModule))
+      .with_global("base", LoxValue::Number(2.0))
+      .with_config(InterpreterConfig {
+        max_call_depth: Some(3),
+      })
+      .with_profiler()
+      .build();
+
+    let (interpreter, engine) = interpret_with(
+      interpreter,
+      "fun twice(n) { return n * base; } print(twice(1));
+      fun deep(n) { return deep(n + 1); } deep(0);",
+    );
+
+    assert_eq!(String::from_utf8(output.borrow().clone()).unwrap(), "2\n");
+    let diagnostics = engine.get_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "Maximum call depth of 3 exceeded");
+    assert_eq!(
+      String::from_utf8(error_output.borrow().clone()).unwrap(),
+      format!("{}\n", diagnostics[0])
+    );
+
+    let profiler = interpreter.profiler.as_ref().unwrap();
+    assert_eq!(profiler.entry("twice").unwrap().calls, 1);
+    assert_eq!(profiler.entry("print").unwrap().calls, 1);
+    // The fourth call is refused before it starts.
+    assert_eq!(profiler.entry("deep").unwrap().calls, 3);
+
+    assert_eq!(
+      interpreter.resolve_import("math").unwrap(),
+      "var pi = 3.14;"
+    );
+    assert!(interpreter.resolve_import("physics").is_err());
+  }
+
+  #[test]
+  fn test_default_interpreter_sets_no_options() {
+    let (interpreter, engine) =
+      interpret("fun down(n) { if (n > 0) return down(n - 1); return n; } down(5);");
+
+    assert!(!engine.has_errors());
+    assert_eq!(interpreter.config, InterpreterConfig::default());
+    assert!(interpreter.profiler.is_none());
+    assert!(interpreter.error_output.is_none());
+    assert!(matches!(
+      interpreter.resolve_import("math"),
+      Err(InterpreterError::RuntimeError(message)) if message.contains("no import resolver")
+    ));
+  }
+
+  #[test]
+  fn test_canonically_equivalent_strings_are_equal() {
+    let precomposed = LoxValue::String("\u{e9}".to_string());
+    let decomposed = LoxValue::String("e\u{301}".to_string());
+
+    assert_eq!(precomposed, decomposed);
+    assert_ne!(precomposed, LoxValue::String("e".to_string()));
+    // The value keeps the text it was written with.
+    assert_eq!(decomposed.to_string(), "e\u{301}");
+  }
+
+  #[test]
+  fn test_print_formats_every_value_type() {
+    let output = Rc::new(RefCell::new(Vec::<u8>::new()));
+    let interpreter = InterpreterBuilder::default()
+      .with_output(output.clone())
+      .build();
+
+    let (_, engine) = interpret_with(
+      interpreter,
+      "class Duck {} fun f() {}
+      print(nil); print(true); print(false); print(42); print(-2.5); print(\"hi\");
+      print(Duck); print(Duck()); print(f); print(clock); print(1, nil);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      String::from_utf8(output.borrow().clone()).unwrap(),
+      "nil\ntrue\nfalse\n42\n-2.5\nhi\nDuck\nDuck instance\n<fn>\n<native fn>\n1 nil\n"
+    );
+  }
+
+  #[test]
+  fn test_negative_number_literals() {
+    let (interpreter, engine) =
+      interpret("var sum = -1 + 2 == 1; var x = -3.5; var grouped = -(2) != -2;");
+
+    assert!(!engine.has_errors());
+    assert!(matches!(
+      global(&interpreter, "sum"),
+      Some(LoxValue::Bool(true))
+    ));
+    assert!(matches!(global(&interpreter, "x"), Some(LoxValue::Number(n)) if n == -3.5));
+    assert!(matches!(
+      global(&interpreter, "grouped"),
+      Some(LoxValue::Bool(false))
+    ));
+  }
+
+  #[test]
+  fn test_scientific_notation_literals() {
+    let (interpreter, engine) = interpret("var a = 1e3 == 1000.0; var b = 1.5e-2 == 0.015;");
+
+    assert!(!engine.has_errors());
+    assert!(matches!(
+      global(&interpreter, "a"),
+      Some(LoxValue::Bool(true))
+    ));
+    assert!(matches!(
+      global(&interpreter, "b"),
+      Some(LoxValue::Bool(true))
+    ));
+  }
+
+  #[test]
+  fn test_format_substitutes_placeholders() {
+    let (interpreter, engine) = interpret(
+      "var none = format(\"plain\");
+      var one = format(\"Hello, {}!\", \"duck\");
+      var many = format(\"{} + {} = {}\", 1, 2, 1 + 2);
+      var extra = format(\"{}\", 1, 2);",
+    );
+
+    assert!(!engine.has_errors());
+    for (name, expected) in [
+      ("none", "plain"),
+      ("one", "Hello, duck!"),
+      ("many", "1 + 2 = 3"),
+      ("extra", "1"),
+    ] {
+      assert!(
+        matches!(global(&interpreter, name), Some(LoxValue::String(s)) if s == expected),
+        "{}",
+        name
+      );
+    }
+  }
+
+  #[test]
+  fn test_format_with_too_few_arguments_is_an_error() {
+    let (interpreter, engine) = interpret("var msg = format(\"{} and {}\", 1);");
+
+    assert_eq!(
+      engine.get_diagnostics()[0].code,
+      DiagnosticCode::RuntimeError
+    );
+    assert!(global(&interpreter, "msg").is_none());
+  }
+
+  #[test]
+  fn test_hash_is_consistent() {
+    // A declaration initialized with a call can't continue with an operator, hence the
+    // reassignments.
+    let (interpreter, engine) = interpret(
+      "var duck = hash(\"duck\");
+      var same = duck == hash(\"du\" + \"ck\");
+      var different = duck == hash(\"goose\");
+      var nan = hash(0 % 0);
+      nan = nan == hash(0 % 0);
+      var zero = hash(0);
+      zero = zero == hash(-0);
+      var arrays = hash([1, \"a\", [nil]]);
+      arrays = arrays == hash([1, \"a\", [nil]]);
+      var order = hash([1, 2]);
+      order = order == hash([2, 1]);
+      var maps = hash({1: 2, 3: 4});
+      maps = maps == hash({3: 4, 1: 2});",
+    );
+
+    assert!(!engine.has_errors());
+    for (name, expected) in [
+      ("same", true),
+      ("different", false),
+      ("nan", true),
+      ("zero", true),
+      ("arrays", true),
+      ("order", false),
+      ("maps", true),
+    ] {
+      assert_eq!(
+        global(&interpreter, name),
+        Some(LoxValue::Bool(expected)),
+        "{}",
+        name
+      );
+    }
+  }
+
+  #[test]
+  fn test_hash_of_function_is_an_error() {
+    let (interpreter, engine) = interpret("fun f() {} var h = hash(f);");
+
+    assert_eq!(
+      engine.get_diagnostics()[0].code,
+      DiagnosticCode::RuntimeError
+    );
+    assert!(global(&interpreter, "h").is_none());
+  }
+
+  #[test]
+  fn test_hash_of_circular_structure_is_an_error() {
+    for source in [
+      "var a = [1]; a.push(a); var h = hash(a);",
+      "var m = {}; m.set(\"self\", [m]); var h = hash(m);",
+    ] {
+      let (interpreter, engine) = interpret(source);
+
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::RuntimeError, "{}", source);
+      assert_eq!(diagnostic.message, "Cannot hash circular structure.");
+      assert!(global(&interpreter, "h").is_none());
+    }
+
+    // The same array twice isn't a cycle.
+    let (_, engine) = interpret("var b = [2]; var h = hash([b, b]);");
+    assert!(!engine.has_errors());
+  }
+
+  #[test]
+  fn test_deepcopy_of_nested_arrays() {
+    let (interpreter, engine) = interpret("var a = [[1, 2], [3, 4]]; var b = deepcopy(a);");
+
+    assert!(!engine.has_errors());
+    let (Some(LoxValue::Array(a)), Some(LoxValue::Array(b))) =
+      (global(&interpreter, "a"), global(&interpreter, "b"))
+    else {
+      panic!("a and b should be arrays");
+    };
+
+    assert_eq!(*a.borrow(), *b.borrow());
+    assert!(!Rc::ptr_eq(&a, &b));
+    for (inner_a, inner_b) in a.borrow().iter().zip(b.borrow().iter()) {
+      let (LoxValue::Array(inner_a), LoxValue::Array(inner_b)) = (inner_a, inner_b) else {
+        panic!("the elements should be arrays");
+      };
+      assert!(!Rc::ptr_eq(inner_a, inner_b));
+    }
+  }
+
+  #[test]
+  fn test_deepcopy_of_map_of_maps() {
+    let output = Rc::new(RefCell::new(Vec::<u8>::new()));
+    let interpreter = InterpreterBuilder::default()
+      .with_output(output.clone())
+      .build();
+
+    let (_, engine) = interpret_with(
+      interpreter,
+      "var a = {\"inner\": {\"x\": 1}}; var b = deepcopy(a);
+      fun clear(inner) { inner.delete(\"x\"); return []; }
+      b.values().flat_map(clear);
+      print(a); print(b);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      String::from_utf8(output.borrow().clone()).unwrap(),
+      "{inner: {x: 1}}\n{inner: {}}\n"
+    );
+  }
+
+  #[test]
+  fn test_deepcopy_of_circular_reference_is_an_error() {
+    let array = LoxValue::array(vec![LoxValue::Number(1.0)]);
+    if let LoxValue::Array(elements) = &array {
+      elements.borrow_mut().push(array.clone());
+    }
+
+    for source in [
+      "var copy = deepcopy(array);",
+      "class Node {} var node = Node(); node.next = node; var copy = deepcopy(node);",
+    ] {
+      let interpreter = InterpreterBuilder::default()
+        .with_global("array", array.clone())
+        .build();
+      let (interpreter, engine) = interpret_with(interpreter, source);
+
+      // Unused variable warnings come first, so look for the error itself.
+      let diagnostic = engine
+        .get_diagnostics()
+        .iter()
+        .find(|diagnostic| diagnostic.code == DiagnosticCode::RuntimeError)
+        .expect("deepcopy should fail");
+      assert_eq!(diagnostic.message, "Circular reference in deepcopy.");
+      assert!(global(&interpreter, "copy").is_none());
+    }
+
+    // Break the cycle so the array can be dropped.
+    if let LoxValue::Array(elements) = &array {
+      elements.borrow_mut().clear();
+    }
+  }
+
+  /// Host method adding its argument to the `count` field of `this`.
+  struct AddMethod;
+
+  impl LoxCallable for AddMethod {
+    fn arity(&self) -> usize {
+      1
+    }
+
+    fn call(
+      &self,
+      _interpreter: &mut Interpreter,
+      arguments: Vec<(LoxValue, Option<Token>)>,
+      _engine: &mut DiagnosticEngine,
+    ) -> Result<LoxValue, InterpreterError> {
+      let (LoxValue::Instance(this), LoxValue::Number(amount)) = (&arguments[0].0, &arguments[1].0)
+      else {
+        return Err(InterpreterError::RuntimeError(
+          "add expects a number".to_string(),
+        ));
+      };
+
+      let mut this = this.borrow_mut();
+      let count = match this.fields.get("count") {
+        Some(LoxValue::Number(count)) => *count,
+        _ => 0.0,
+      };
+      this
+        .fields
+        .insert("count".to_string(), LoxValue::Number(count + amount));
+      Ok(LoxValue::Number(count + amount))
+    }
+  }
+
+  #[test]
+  fn test_define_class_from_the_host() {
+    let mut interpreter = Interpreter::new();
+    interpreter.define_class(
+      "Counter",
+      &[
+        ("count", LoxValue::Number(0.0)),
+        ("label", LoxValue::String("clicks".to_string())),
+      ],
+      vec![("add", Box::new(AddMethod))],
+    );
+
+    let (interpreter, engine) = interpret_with(
+      interpreter,
+      "var a = Counter(); var b = Counter();
+      a.add(2); var total = a.add(3);
+      var a_count = a.count; var b_count = b.count; var label = b.label;",
+    );
+
+    assert!(!engine.has_errors());
+    for (name, expected) in [
+      ("total", LoxValue::Number(5.0)),
+      ("a_count", LoxValue::Number(5.0)),
+      ("b_count", LoxValue::Number(0.0)),
+      ("label", LoxValue::String("clicks".to_string())),
+    ] {
+      assert_eq!(global(&interpreter, name), Some(expected), "{}", name);
+    }
+  }
+
+  #[test]
+  fn test_define_class_with_init() {
+    let mut interpreter = Interpreter::new();
+    interpreter.define_class(
+      "Counter",
+      &[("count", LoxValue::Number(0.0))],
+      vec![("init", Box::new(AddMethod)), ("add", Box::new(AddMethod))],
+    );
+
+    let (interpreter, engine) = interpret_with(
+      interpreter,
+      "var counter = Counter(10); counter.add(1); var count = counter.count;",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "count"), Some(LoxValue::Number(11.0)));
+  }
+
+  #[test]
+  fn test_snapshot_globals() {
+    let (interpreter, engine) = interpret(
+      "var number = 1.5; var text = \"duck\"; var flag = true; var nothing;
+      fun f() {} { var local = 1; }",
+    );
+
+    assert!(!engine.has_errors());
+    let globals = interpreter.snapshot_globals();
+    assert_eq!(globals["number"], LoxValue::Number(1.5));
+    assert_eq!(globals["text"], LoxValue::String("duck".to_string()));
+    assert_eq!(globals["flag"], LoxValue::Bool(true));
+    assert_eq!(globals["nothing"], LoxValue::Nil);
+    assert!(matches!(globals["f"], LoxValue::Function(_)));
+    assert!(matches!(globals["print"], LoxValue::NativeFunction(_)));
+    assert!(matches!(globals["Map"], LoxValue::NativeClass(_)));
+    assert!(!globals.contains_key("local"));
+  }
+
+  #[test]
+  fn test_snapshot_globals_is_not_changed_by_the_program() {
+    let (mut interpreter, engine) =
+      interpret("var inner = [2]; var list = [1, inner]; var map = {};");
+    assert!(!engine.has_errors());
+
+    let snapshot = interpreter.snapshot_globals();
+    for source in ["list.push(3)", "inner.push(4)", "map.set(\"k\", 1)"] {
+      interpreter.evaluate_expression(source).unwrap();
+    }
+
+    assert_eq!(snapshot["list"].to_lox_string(), "[1, [2]]");
+    assert_eq!(snapshot["map"].to_lox_string(), "{}");
+    assert_eq!(
+      interpreter.snapshot_globals()["list"].to_lox_string(),
+      "[1, [2, 4], 3]"
+    );
+  }
+
+  #[test]
+  fn test_snapshot_globals_shares_a_container_holding_itself() {
+    let (mut interpreter, engine) = interpret("var a = [1]; a.push(a);");
+    assert!(!engine.has_errors());
+
+    let snapshot = interpreter.snapshot_globals();
+    interpreter.evaluate_expression("a.push(2)").unwrap();
+
+    let (LoxValue::Array(copy), Some(LoxValue::Array(original))) =
+      (&snapshot["a"], global(&interpreter, "a"))
+    else {
+      panic!("a should be an array");
+    };
+    assert!(Rc::ptr_eq(copy, &original));
+    assert_eq!(snapshot["a"].to_lox_string(), "[1, [...], 2]");
+
+    // Break the cycle so the array can be dropped.
+    original.borrow_mut().clear();
+  }
+
+  #[test]
+  fn test_evaluate_expression() {
+    let mut interpreter = Interpreter::new();
+
+    assert_eq!(
+      interpreter.evaluate_expression("1 + 2 * 3").unwrap(),
+      LoxValue::Number(7.0)
+    );
+    assert_eq!(
+      interpreter.evaluate_expression("\"du\" + \"ck\"").unwrap(),
+      LoxValue::String("duck".to_string())
+    );
+  }
+
+  #[test]
+  fn test_evaluate_expression_sees_globals() {
+    let (mut interpreter, engine) = interpret("var answer = 40; fun add(a, b) { return a + b; }");
+    assert!(!engine.has_errors());
+
+    assert_eq!(
+      interpreter.evaluate_expression("add(answer, 2)").unwrap(),
+      LoxValue::Number(42.0)
+    );
+  }
+
+  #[test]
+  fn test_evaluate_expression_sees_natives_before_any_run() {
+    let mut interpreter = Interpreter::new();
+
+    assert_eq!(
+      interpreter
+        .evaluate_expression("format(\"{} {}\", 1, \"duck\")")
+        .unwrap(),
+      LoxValue::String("1 duck".to_string())
+    );
+    assert!(matches!(
+      interpreter.evaluate_expression("clock()").unwrap(),
+      LoxValue::Number(_)
+    ));
+    assert_eq!(
+      interpreter
+        .evaluate_expression("hash(1) == hash(1)")
+        .unwrap(),
+      LoxValue::Bool(true)
+    );
+  }
+
+  #[test]
+  fn test_evaluate_expression_ignores_the_locals_of_the_last_run() {
+    // The `a` read in the inner block sits at the same offset as the `a` of the expression.
+    let (mut interpreter, engine) = interpret("{ var a = 1; { a; } } var a = 2;");
+    assert!(!engine.has_errors());
+
+    assert_eq!(
+      interpreter
+        .evaluate_expression(&format!("{}a", " ".repeat(15)))
+        .unwrap(),
+      LoxValue::Number(2.0)
+    );
+    assert_eq!(
+      interpreter.evaluate_expression("a + 1").unwrap(),
+      LoxValue::Number(3.0)
+    );
+  }
+
+  #[test]
+  fn test_evaluate_expression_diagnostics_point_into_its_source() {
+    let (mut interpreter, engine) = interpret("{ var a = 1; { a; } }");
+    assert!(!engine.has_errors());
+
+    // The `/` of the division, the `;` the parser stops at.
+    for (source, byte_offset) in [("1 / 0", 2), ("1 + 2;", 5)] {
+      let diagnostics = interpreter.evaluate_expression(source).unwrap_err();
+      assert_eq!(
+        diagnostics[0].labels[0].span.byte_offset, byte_offset,
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn test_evaluate_expression_errors() {
+    let mut interpreter = Interpreter::new();
+
+    let diagnostics = interpreter.evaluate_expression("1 / 0").unwrap_err();
+    assert_eq!(diagnostics[0].code, DiagnosticCode::DivisionByZero);
+
+    let diagnostics = interpreter.evaluate_expression("missing").unwrap_err();
+    assert_eq!(diagnostics[0].code, DiagnosticCode::UndeclaredVariable);
+
+    let diagnostics = interpreter
+      .evaluate_expression("format(\"{}\")")
+      .unwrap_err();
+    assert_eq!(diagnostics[0].code, DiagnosticCode::RuntimeError);
+
+    let diagnostics = interpreter.evaluate_expression("1 + 2;").unwrap_err();
+    assert_eq!(diagnostics[0].code, DiagnosticCode::UnexpectedToken);
+  }
+
+  #[test]
+  fn test_else_if_chain_takes_one_branch() {
+    for (flags, taken) in [
+      ("true, true, true", 1.0),
+      ("true, false, false", 1.0),
+      ("false, true, true", 2.0),
+      ("false, false, true", 3.0),
+      ("false, false, false", 4.0),
+    ] {
+      let (interpreter, engine) = interpret(&format!(
+        "var taken = 0; fun pick(a, b, c) {{
+          if (a) taken = 1; else if (b) taken = 2; else if (c) taken = 3; else taken = 4;
+        }}
+        pick({});",
+        flags
+      ));
+
+      assert!(!engine.has_errors(), "{}", flags);
+      assert_eq!(
+        global(&interpreter, "taken"),
+        Some(LoxValue::Number(taken)),
+        "{}",
+        flags
+      );
+    }
+  }
+
+  #[test]
+  fn test_logical_operators_short_circuit() {
+    let (interpreter, engine) = interpret(
+      "var calls = 0; fun boom() { calls = calls + 1; return true; }
+      var a = false and boom(); var b = true or boom(); var c = nil or 7;
+      var d = nil && boom(); var e = 1 || boom();",
+    );
+
+    assert!(!engine.has_errors());
+    let globals = interpreter.snapshot_globals();
+    assert_eq!(globals["calls"], LoxValue::Number(0.0));
+    assert_eq!(globals["a"], LoxValue::Bool(false));
+    assert_eq!(globals["b"], LoxValue::Bool(true));
+    assert_eq!(globals["c"], LoxValue::Number(7.0));
+    assert_eq!(globals["d"], LoxValue::Nil);
+    assert_eq!(globals["e"], LoxValue::Number(1.0));
+  }
+}
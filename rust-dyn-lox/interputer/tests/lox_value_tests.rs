@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+  use compiler::{
+    lox_value::{InterpreterError, LoxValue},
+    map::{MapEntries, MapKey},
+  };
+
+  fn samples() -> Vec<LoxValue> {
+    let key = LoxValue::String("k".to_string());
+    let entries = MapEntries::from([(
+      MapKey::from_value(&key).unwrap(),
+      (key, LoxValue::Number(1.0)),
+    )]);
+
+    vec![
+      LoxValue::Nil,
+      LoxValue::Number(2.5),
+      LoxValue::String("duck".to_string()),
+      LoxValue::Bool(false),
+      LoxValue::array(vec![LoxValue::Nil, LoxValue::Bool(true)]),
+      LoxValue::map(entries),
+    ]
+  }
+
+  #[test]
+  fn test_accessors_only_match_their_own_type() {
+    for (index, value) in samples().iter().enumerate() {
+      assert_eq!(
+        value.as_number(),
+        (index == 1).then_some(2.5),
+        "{:?}",
+        value
+      );
+      assert_eq!(
+        value.as_string(),
+        (index == 2).then_some("duck"),
+        "{:?}",
+        value
+      );
+      assert_eq!(
+        value.as_bool(),
+        (index == 3).then_some(false),
+        "{:?}",
+        value
+      );
+      assert_eq!(
+        value.as_array().map(|elements| elements.len()),
+        (index == 4).then_some(2),
+        "{:?}",
+        value
+      );
+      assert_eq!(
+        value.as_map().map(|entries| entries.len()),
+        (index == 5).then_some(1),
+        "{:?}",
+        value
+      );
+    }
+  }
+
+  #[test]
+  fn test_expect_number() {
+    assert_eq!(LoxValue::Number(3.0).expect_number("sqrt").unwrap(), 3.0);
+
+    let Err(InterpreterError::RuntimeError(message)) =
+      LoxValue::String("duck".to_string()).expect_number("sqrt")
+    else {
+      panic!("expected a runtime error");
+    };
+    assert_eq!(message, "sqrt expects a number, got duck");
+  }
+}
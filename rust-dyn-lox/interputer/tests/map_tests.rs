@@ -0,0 +1,257 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use compiler::lox_value::LoxValue;
+  use diagnostic::diagnostic_code::DiagnosticCode;
+
+  use crate::common::{global, interpret};
+
+  #[test]
+  fn test_empty_map() {
+    let (interpreter, engine) = interpret(
+      "var m = {}; var keys = m.keys(); var values = m.values(); var entries = m.entries();
+      var has = m.has(1); var deleted = m.delete(1);",
+    );
+
+    assert!(!engine.has_errors());
+    for name in ["keys", "values", "entries"] {
+      assert_eq!(global(&interpreter, name), "[]");
+    }
+    assert_eq!(global(&interpreter, "has"), "false");
+    assert_eq!(global(&interpreter, "deleted"), "false");
+  }
+
+  #[test]
+  fn test_map_with_one_entry() {
+    let (interpreter, engine) = interpret(
+      "var m = {\"a\": 1}; var keys = m.keys(); var values = m.values();
+      var entries = m.entries(); var has = m.has(\"a\"); var missing = m.has(\"b\");
+      var after = m;",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "keys"), "[a]");
+    assert_eq!(global(&interpreter, "values"), "[1]");
+    assert_eq!(global(&interpreter, "entries"), "[[a, 1]]");
+    assert_eq!(global(&interpreter, "has"), "true");
+    assert_eq!(global(&interpreter, "missing"), "false");
+    // Reading keys, values and entries leaves the map as it was.
+    assert_eq!(global(&interpreter, "after"), "{a: 1}");
+  }
+
+  #[test]
+  fn test_map_with_mixed_key_types() {
+    let (interpreter, engine) = interpret(
+      "var m = {1: \"number\", \"1\": \"string\", true: \"bool\", nil: \"nil\"};
+      var keys = m.keys(); var values = m.values();
+      var all = [m.has(1), m.has(\"1\"), m.has(true), m.has(nil)];
+      var deleted = m.delete(1); var rest = m.values(); var gone = m.has(1);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "keys"), "[1, 1, true, nil]");
+    assert_eq!(
+      global(&interpreter, "values"),
+      "[number, string, bool, nil]"
+    );
+    assert_eq!(global(&interpreter, "all"), "[true, true, true, true]");
+    assert_eq!(global(&interpreter, "deleted"), "true");
+    assert_eq!(global(&interpreter, "rest"), "[string, bool, nil]");
+    assert_eq!(global(&interpreter, "gone"), "false");
+  }
+
+  #[test]
+  fn test_entries_keep_insertion_order() {
+    let (interpreter, engine) = interpret(
+      "var m = {\"z\": 1, \"a\": 2, \"m\": 3}; m.set(\"b\", 4); m.set(\"z\", 5);
+      m.delete(\"a\"); m.set(\"a\", 6); var keys = m.keys(); var values = m.values();
+      var back = Map.from_entries(m.entries());",
+    );
+
+    assert!(!engine.has_errors());
+    // Overwriting a key keeps its place, deleting and setting it again moves it last.
+    assert_eq!(global(&interpreter, "keys"), "[z, m, b, a]");
+    assert_eq!(global(&interpreter, "values"), "[5, 3, 4, 6]");
+    assert_eq!(global(&interpreter, "back"), "{z: 5, m: 3, b: 4, a: 6}");
+  }
+
+  #[test]
+  fn test_unhashable_key_is_a_type_error() {
+    let (_, engine) = interpret("var m = {[1]: 2};");
+
+    assert_eq!(engine.get_diagnostics()[0].code, DiagnosticCode::TypeError);
+  }
+
+  #[test]
+  fn test_merge_without_overlap() {
+    let (interpreter, engine) = interpret(
+      "var a = {1: 1}; var b = {2: 2}; var merged = a.merge(b); var keys = merged.keys();",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "keys"), "[1, 2]");
+    assert_eq!(global(&interpreter, "a"), "{1: 1}");
+    assert_eq!(global(&interpreter, "b"), "{2: 2}");
+  }
+
+  #[test]
+  fn test_merge_with_overlap_takes_the_other_value() {
+    let (interpreter, engine) = interpret(
+      "var a = {\"k\": \"old\", 1: 1}; var merged = a.merge({\"k\": \"new\"});
+      var entries = merged.entries();",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "entries"), "[[k, new], [1, 1]]");
+    assert_eq!(global(&interpreter, "a"), "{k: old, 1: 1}");
+  }
+
+  #[test]
+  fn test_clone_is_independent() {
+    let (interpreter, engine) = interpret(
+      "var a = {1: 1, 2: 2}; var b = a.clone(); b.delete(1); var alias = a; alias.delete(2);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "{1: 1}");
+    assert_eq!(global(&interpreter, "b"), "{2: 2}");
+  }
+
+  #[test]
+  fn test_from_entries_inverts_entries() {
+    let (interpreter, engine) = interpret(
+      "var m = {\"a\": 1, nil: [2]}; var back = Map.from_entries(m.entries());
+      var same = back == m; var built = Map.from_entries([[\"x\", 1]]);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "same"), "true");
+    assert_eq!(global(&interpreter, "built"), "{x: 1}");
+  }
+
+  #[test]
+  fn test_group_by_string_keys() {
+    let (interpreter, engine) = interpret(
+      "fun parity(n) { if (n % 2 == 0) return \"even\"; return \"odd\"; }
+      var groups = [1, 2, 3, 4, 5].group_by(parity);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      global(&interpreter, "groups"),
+      "{odd: [1, 3, 5], even: [2, 4]}"
+    );
+  }
+
+  #[test]
+  fn test_group_by_numeric_keys() {
+    let (interpreter, engine) = interpret(
+      "fun remainder(n) { return n % 3; }
+      var groups = [3, 1, 4, 1, 5, 9, 2, 6].group_by(remainder);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      global(&interpreter, "groups"),
+      "{0: [3, 9, 6], 1: [1, 4, 1], 2: [5, 2]}"
+    );
+  }
+
+  #[test]
+  fn test_group_by_of_empty_array() {
+    let (interpreter, engine) =
+      interpret("fun key(n) { return n; } var groups = [].group_by(key);");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "groups"), "{}");
+  }
+
+  #[test]
+  fn test_group_by_field() {
+    let (interpreter, engine) = interpret(
+      "class Pet { init(kind, name) { this.kind = kind; this.name = name; } }
+      fun kind(pet) { return pet.kind; }
+      var pets = [Pet(\"cat\", \"Tom\"), Pet(\"dog\", \"Rex\"), Pet(\"cat\", \"Kit\")];
+      var groups = pets.group_by(kind);",
+    );
+
+    assert!(!engine.has_errors());
+
+    let Some(LoxValue::Map(groups)) = interpreter.env.borrow().get("groups") else {
+      panic!("groups is not a map");
+    };
+    let names = groups
+      .borrow()
+      .values()
+      .map(|(kind, pets)| {
+        let LoxValue::Array(pets) = pets else {
+          panic!("group {} is not an array", kind);
+        };
+        let pets = pets
+          .borrow()
+          .iter()
+          .map(|pet| match pet {
+            LoxValue::Instance(pet) => pet.borrow().fields["name"].to_lox_string(),
+            _ => panic!("{} is not a pet", pet),
+          })
+          .collect::<Vec<_>>();
+        (kind.to_lox_string(), pets)
+      })
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      names,
+      vec![
+        (
+          "cat".to_string(),
+          vec!["Tom".to_string(), "Kit".to_string()]
+        ),
+        ("dog".to_string(), vec!["Rex".to_string()]),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_set() {
+    let (interpreter, engine) =
+      interpret("var m = {1: \"one\"}; m.set(1, \"uno\"); m.set(2, \"dos\");");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "m"), "{1: uno, 2: dos}");
+  }
+
+  #[test]
+  fn test_frozen_map_rejects_mutation() {
+    for mutation in ["m.set(\"b\", 2);", "m.delete(\"a\");"] {
+      let (interpreter, engine) = interpret(&format!("var m = freeze({{\"a\": 1}}); {}", mutation));
+
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::RuntimeError);
+      assert_eq!(diagnostic.message, "Cannot mutate frozen object.");
+      assert_eq!(global(&interpreter, "m"), "{a: 1}");
+    }
+  }
+
+  #[test]
+  fn test_map_holding_itself_prints() {
+    let (interpreter, engine) = interpret("var m = {}; m.set(\"self\", m); print(m); var l = [m];");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "m"), "{self: {...}}");
+    assert_eq!(global(&interpreter, "l"), "[{self: {...}}]");
+  }
+
+  #[test]
+  fn test_frozen_map_can_be_read() {
+    let (interpreter, engine) = interpret(
+      "var m = freeze({\"a\": {\"b\": 1}}); var has = m.has(\"a\");
+      var frozen = is_frozen(m); var merged = m.merge({\"c\": 2}); merged.set(\"d\", 3);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "has"), "true");
+    assert_eq!(global(&interpreter, "frozen"), "true");
+    assert_eq!(global(&interpreter, "merged"), "{a: {b: 1}, c: 2, d: 3}");
+  }
+}
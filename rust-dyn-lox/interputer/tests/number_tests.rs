@@ -0,0 +1,94 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use diagnostic::diagnostic_code::DiagnosticCode;
+
+  use crate::common::{global, interpret};
+
+  #[test]
+  fn test_to_string_in_radix() {
+    let (interpreter, engine) = interpret(
+      "var binary = (10).to_string(2); var octal = (8).to_string(8);
+      var hex = (255).to_string(16); var base36 = (35).to_string(36);
+      var negative = (-255).to_string(16);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "binary"), "1010");
+    assert_eq!(global(&interpreter, "octal"), "10");
+    assert_eq!(global(&interpreter, "hex"), "ff");
+    assert_eq!(global(&interpreter, "base36"), "z");
+    assert_eq!(global(&interpreter, "negative"), "-ff");
+  }
+
+  #[test]
+  fn test_to_string_defaults_to_radix_10() {
+    let (interpreter, engine) =
+      interpret("var int = (42).to_string(); var float = (2.5).to_string(10);");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "int"), "42");
+    assert_eq!(global(&interpreter, "float"), "2.5");
+  }
+
+  #[test]
+  fn test_radix_out_of_range_is_an_error() {
+    for radix in ["1", "37"] {
+      let (_, engine) = interpret(&format!("(10).to_string({});", radix));
+
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::RuntimeError);
+      assert_eq!(
+        diagnostic.message,
+        format!("Radix must be between 2 and 36, got {}", radix)
+      );
+    }
+  }
+
+  #[test]
+  fn test_is_nan() {
+    // There is no `Math.sqrt`, `0 % 0` is the way to get NaN.
+    let (interpreter, engine) = interpret(
+      "var nan = 0 % 0; var a = Number.is_nan(nan); var b = Number.is_nan(1);
+      var c = Number.is_nan(\"nan\"); var d = Number.is_finite(nan);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "true");
+    assert_eq!(global(&interpreter, "b"), "false");
+    assert_eq!(global(&interpreter, "c"), "false");
+    assert_eq!(global(&interpreter, "d"), "false");
+  }
+
+  #[test]
+  fn test_is_finite() {
+    // `1 / 0` is a division by zero error, an out of range literal overflows to infinity.
+    let (interpreter, engine) = interpret(
+      "var a = Number.is_finite(1e309); var b = Number.is_finite(-1e309);
+      var c = Number.is_finite(1.5); var d = Number.is_finite(nil);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "false");
+    assert_eq!(global(&interpreter, "b"), "false");
+    assert_eq!(global(&interpreter, "c"), "true");
+    assert_eq!(global(&interpreter, "d"), "false");
+  }
+
+  #[test]
+  fn test_is_integer() {
+    let (interpreter, engine) = interpret(
+      "var a = Number.is_integer(42.0); var b = Number.is_integer(42.5);
+      var c = Number.is_integer(-0); var d = Number.is_integer(1e309);
+      var e = Number.is_integer(1e300);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "true");
+    assert_eq!(global(&interpreter, "b"), "false");
+    assert_eq!(global(&interpreter, "c"), "true");
+    assert_eq!(global(&interpreter, "d"), "false");
+    assert_eq!(global(&interpreter, "e"), "true");
+  }
+}
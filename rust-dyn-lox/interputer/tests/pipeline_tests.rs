@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+  use std::{cell::Cell, env, process::Command, rc::Rc};
+
+  use compiler::{
+    function::LoxCallable,
+    interpreter::{Interpreter, InterpreterBuilder},
+    lox_value::{InterpreterError, LoxValue},
+    runner::Runner,
+  };
+  use diagnostic::{diagnostic_code::DiagnosticCode, DiagnosticEngine};
+  use scanner::token::Token;
+
+  /// Native function that records whether the program ever called it.
+  struct Probe(Rc<Cell<bool>>);
+
+  impl LoxCallable for Probe {
+    fn arity(&self) -> usize {
+      0
+    }
+
+    fn call(
+      &self,
+      _interpreter: &mut Interpreter,
+      _arguments: Vec<(LoxValue, Option<Token>)>,
+      _engine: &mut DiagnosticEngine,
+    ) -> Result<LoxValue, InterpreterError> {
+      self.0.set(true);
+      Ok(LoxValue::Nil)
+    }
+  }
+
+  /// Runs `source` through the whole pipeline and returns whether `probe()` was called.
+  fn run(source: &str) -> (bool, DiagnosticEngine) {
+    let called = Rc::new(Cell::new(false));
+    let mut interpreter = InterpreterBuilder::default()
+      .with_global(
+        "probe",
+        LoxValue::NativeFunction(Rc::new(Probe(called.clone()))),
+      )
+      .build();
+
+    let mut engine = DiagnosticEngine::new();
+    Runner::new().inturpret_with(source.to_string(), &mut interpreter, &mut engine);
+
+    (called.get(), engine)
+  }
+
+  #[test]
+  fn test_interpreter_runs_without_errors() {
+    let (called, engine) = run("probe();");
+
+    assert!(engine.proceed());
+    assert!(called);
+  }
+
+  #[test]
+  fn test_scan_error_stops_the_pipeline() {
+    let (called, engine) = run("probe(); @");
+
+    assert!(!engine.proceed());
+    assert_eq!(
+      engine.get_diagnostics()[0].code,
+      DiagnosticCode::InvalidCharacter
+    );
+    assert!(!called);
+  }
+
+  #[test]
+  fn test_parse_error_stops_the_pipeline() {
+    let (called, engine) = run("probe(); var = 1;");
+
+    assert!(!engine.proceed());
+    assert!(!called);
+  }
+
+  #[test]
+  fn test_run_succeeds_without_errors() {
+    assert!(Runner::new().run("print(1);", None).is_ok());
+  }
+
+  #[test]
+  fn test_run_returns_the_errors_of_the_failing_stage() {
+    for (source, code) in [
+      ("var a = @;", DiagnosticCode::InvalidCharacter),
+      ("var = 1;", DiagnosticCode::ExpectedIdentifier),
+      ("return 1;", DiagnosticCode::ReturnNotInFunction),
+      ("print(-\"a\");", DiagnosticCode::TypeError),
+    ] {
+      let diagnostics = Runner::new().run(source, Some("main.duck")).unwrap_err();
+
+      assert_eq!(diagnostics[0].code, code, "{}", source);
+      assert_eq!(
+        diagnostics[0].labels[0].span.file, "main.duck",
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn test_run_returns_every_diagnostic() {
+    let diagnostics = Runner::new()
+      .run("var a = @; var b = #;", None)
+      .unwrap_err();
+
+    let codes: Vec<_> = diagnostics
+      .iter()
+      .map(|diagnostic| diagnostic.code)
+      .collect();
+    assert_eq!(
+      codes,
+      [
+        DiagnosticCode::InvalidCharacter,
+        DiagnosticCode::InvalidCharacter
+      ]
+    );
+  }
+
+  /// Set when `test_run_prints_nothing` starts itself again to watch its stdout.
+  const RUN_CHILD: &str = "DUCK_RUN_CHILD";
+
+  #[test]
+  fn test_run_prints_nothing() {
+    if env::var_os(RUN_CHILD).is_some() {
+      print!("<run>");
+      Runner::new().run("var a = 1;", None).unwrap();
+      Runner::new().run("var = 1;", None).unwrap_err();
+      Runner::new().run("var a = @;", None).unwrap_err();
+      print!("</run>");
+      return;
+    }
+
+    let output = Command::new(env::current_exe().unwrap())
+      .args(["--exact", "tests::test_run_prints_nothing", "--nocapture"])
+      .env(RUN_CHILD, "1")
+      .output()
+      .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success(), "{}", stdout);
+    let start = stdout.find("<run>").unwrap() + "<run>".len();
+    let end = stdout.find("</run>").unwrap();
+    assert_eq!(&stdout[start..end], "", "{}", stdout);
+  }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use diagnostic::diagnostic_code::DiagnosticCode;
+
+  use crate::common::{global, interpret};
+
+  #[test]
+  fn test_prototype_chain() {
+    let (interpreter, engine) = interpret(
+      "class Object {} var base = Object(); base.greeting = \"hello\"; base.kind = \"base\";
+      var middle = Object(); middle.kind = \"middle\"; set_prototype(middle, base);
+      var leaf = Object(); set_prototype(leaf, middle);
+      var greeting = leaf.greeting; var kind = leaf.kind;",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "greeting"), "hello");
+    assert_eq!(global(&interpreter, "kind"), "middle");
+  }
+
+  #[test]
+  fn test_own_field_shadows_the_prototype() {
+    let (interpreter, engine) = interpret(
+      "class Object {} var proto = Object(); proto.name = \"proto\";
+      var object = Object(); set_prototype(object, proto); object.name = \"own\";
+      var own = object.name; var shared = proto.name;",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "own"), "own");
+    assert_eq!(global(&interpreter, "shared"), "proto");
+  }
+
+  #[test]
+  fn test_prototype_changes_are_visible() {
+    let (interpreter, engine) = interpret(
+      "class Object {} var proto = Object(); var object = Object();
+      set_prototype(object, proto); proto.late = 1; var late = object.late;",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "late"), "1");
+  }
+
+  #[test]
+  fn test_cyclic_prototype_is_an_error() {
+    let (_, engine) = interpret(
+      "class Object {} var a = Object(); var b = Object();
+      set_prototype(a, b); set_prototype(b, a);",
+    );
+
+    // The resolver warns about `a` first, so look for the error itself.
+    let diagnostic = engine
+      .get_diagnostics()
+      .iter()
+      .find(|diagnostic| diagnostic.code == DiagnosticCode::RuntimeError)
+      .expect("the second set_prototype should fail");
+    assert_eq!(diagnostic.message, "Prototype chains can't be cyclic.");
+  }
+
+  #[test]
+  fn test_removed_prototype_is_no_longer_read() {
+    let (_, engine) = interpret(
+      "class Object {} var proto = Object(); proto.name = \"proto\"; var object = Object();
+      set_prototype(object, proto); set_prototype(object, nil); print(object.name);",
+    );
+
+    let diagnostic = engine
+      .get_diagnostics()
+      .iter()
+      .find(|diagnostic| diagnostic.code == DiagnosticCode::RuntimeError)
+      .expect("reading the field should fail");
+    assert_eq!(diagnostic.message, "Undefined property 'name'");
+  }
+}
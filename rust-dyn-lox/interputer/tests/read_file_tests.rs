@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+  use std::{fs, path::PathBuf};
+
+  use compiler::{lox_value::InterpreterError, runner::Runner};
+
+  fn temp_source(name: &str, source: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}-{}.duck", name, std::process::id()));
+    fs::write(&path, source).unwrap();
+    path
+  }
+
+  #[test]
+  fn test_read_file() {
+    let path = temp_source("read_sync", "print(1);\n");
+
+    let source = Runner::read_file(path.to_str().unwrap()).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(source, "print(1);\n");
+  }
+
+  #[test]
+  fn test_read_file_from_a_path() {
+    let path = temp_source("read_path", "print(2);\n");
+
+    let source = Runner::read_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(source, "print(2);\n");
+  }
+
+  #[test]
+  fn test_read_missing_file() {
+    assert!(Runner::read_file("does/not/exist.duck").is_err());
+  }
+
+  #[test]
+  fn test_errors_convert_with_question_mark() {
+    fn read_number(path: &str) -> Result<f64, InterpreterError> {
+      Ok(Runner::read_file(path)?.trim().parse::<f64>()?)
+    }
+
+    let path = temp_source("read_number", "12.5\n");
+    assert_eq!(read_number(path.to_str().unwrap()).unwrap(), 12.5);
+    fs::write(&path, "twelve").unwrap();
+    let invalid = read_number(path.to_str().unwrap()).unwrap_err();
+    fs::remove_file(&path).unwrap();
+
+    assert!(
+      matches!(invalid, InterpreterError::RuntimeError(message) if message.starts_with("Invalid literal"))
+    );
+    assert!(matches!(
+      read_number("does/not/exist.duck"),
+      Err(InterpreterError::RuntimeError(message)) if message.starts_with("IO error")
+    ));
+
+    let from_int: InterpreterError = "x".parse::<i64>().unwrap_err().into();
+    assert!(matches!(from_int, InterpreterError::RuntimeError(_)));
+
+    let boxed: Box<dyn std::error::Error> = Box::new(from_int);
+    assert!(boxed
+      .to_string()
+      .starts_with("runtime error: Invalid literal"));
+  }
+
+  #[cfg(feature = "async")]
+  #[tokio::test]
+  async fn test_read_file_async() {
+    let path = temp_source("read_async", "var a = `multi\nline`;\n");
+
+    let source = Runner::read_file_async(path.to_str().unwrap())
+      .await
+      .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(source, "var a = `multi\nline`;\n");
+  }
+}
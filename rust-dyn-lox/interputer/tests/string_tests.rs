@@ -0,0 +1,113 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use diagnostic::diagnostic_code::DiagnosticCode;
+
+  use crate::common::{global, interpret};
+
+  #[test]
+  fn test_pad_numbers_and_shorter_strings() {
+    let (interpreter, engine) = interpret(
+      "var number = \"42\".pad_start(5, \"0\"); var end = \"hi\".pad_end(5, \"-\");
+      var exact = \"abc\".pad_start(3, \"*\");",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "number"), "00042");
+    assert_eq!(global(&interpreter, "end"), "hi---");
+    assert_eq!(global(&interpreter, "exact"), "abc");
+  }
+
+  #[test]
+  fn test_pad_longer_string_is_unchanged() {
+    let (interpreter, engine) =
+      interpret("var a = \"longer\".pad_start(2, \"0\"); var b = \"longer\".pad_end(0);");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "longer");
+    assert_eq!(global(&interpreter, "b"), "longer");
+  }
+
+  #[test]
+  fn test_pad_defaults_to_spaces() {
+    let (interpreter, engine) =
+      interpret("var a = \"x\".pad_start(3) + \"|\"; var b = \"x\".pad_end(3) + \"|\";");
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "a"), "  x|");
+    assert_eq!(global(&interpreter, "b"), "x  |");
+  }
+
+  #[test]
+  fn test_multi_char_padding_is_an_error() {
+    let (interpreter, engine) = interpret("var a = \"x\".pad_start(4, \"ab\");");
+
+    let diagnostic = &engine.get_diagnostics()[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::RuntimeError);
+    assert_eq!(
+      diagnostic.message,
+      "Padding character must be a single character."
+    );
+    assert!(interpreter.env.borrow().get("a").is_none());
+  }
+
+  #[test]
+  fn test_code_point_round_trip() {
+    let (interpreter, engine) = interpret(
+      "var code = \"A\".code_point_at(0); var second = \"AB\".code_point_at(1);
+      var past_end = \"A\".code_point_at(1); var letter = String.from_code_point(65);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "code"), "65");
+    assert_eq!(global(&interpreter, "second"), "66");
+    assert_eq!(global(&interpreter, "past_end"), "nil");
+    assert_eq!(global(&interpreter, "letter"), "A");
+  }
+
+  #[test]
+  fn test_emoji_code_point() {
+    let (interpreter, engine) = interpret(
+      "var emoji = String.from_code_point(128512); var code = emoji.code_point_at(0);
+      var after = emoji.code_point_at(1);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "emoji"), "\u{1F600}");
+    assert_eq!(global(&interpreter, "code"), "128512");
+    assert_eq!(global(&interpreter, "after"), "nil");
+  }
+
+  #[test]
+  fn test_invalid_code_point_is_an_error() {
+    for code_point in ["1114112", "55296", "-1"] {
+      let (_, engine) = interpret(&format!("String.from_code_point({});", code_point));
+
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::RuntimeError);
+      assert!(diagnostic.message.starts_with("Invalid code point"));
+    }
+  }
+
+  #[test]
+  fn test_template_string_interpolation() {
+    let (interpreter, engine) = interpret(
+      "var name = \"duck\"; fun count() { return 2; }
+      var greeting = `hi ${name}, you have ${count() + 1} ${`nested ${name}`}`;",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      global(&interpreter, "greeting"),
+      "hi duck, you have 3 nested duck"
+    );
+  }
+
+  #[test]
+  fn test_empty_interpolation_is_a_parse_error() {
+    let (_, engine) = interpret("var broken = `a ${}`;");
+
+    assert!(engine.has_errors());
+  }
+}
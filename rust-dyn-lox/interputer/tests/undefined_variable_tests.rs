@@ -0,0 +1,25 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use diagnostic::diagnostic_code::DiagnosticCode;
+
+  use crate::common::interpret;
+
+  #[test]
+  fn test_reading_undeclared_variable_is_an_error() {
+    let (_, engine) = interpret("print(undeclared_var);");
+
+    let diagnostics = engine.get_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, DiagnosticCode::UndeclaredVariable);
+    assert!(diagnostics[0].message.contains("undeclared_var"));
+  }
+
+  #[test]
+  fn test_reading_nil_variable_is_not_an_error() {
+    let (_, engine) = interpret("var declared; print(declared);");
+
+    assert!(!engine.has_errors());
+  }
+}
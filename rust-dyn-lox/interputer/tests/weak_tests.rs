@@ -0,0 +1,55 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use diagnostic::diagnostic_code::DiagnosticCode;
+
+  use crate::common::{global, interpret};
+
+  #[test]
+  fn test_deref_while_alive() {
+    let (interpreter, engine) = interpret(
+      "class Point { init(x) { this.x = x; } }
+      var p = Point(1); var w = weakref(p);
+      var x = w.deref().x; var dead = is_dead(w);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "x"), "1");
+    assert_eq!(global(&interpreter, "dead"), "false");
+    assert_eq!(global(&interpreter, "w"), "<weakref>");
+  }
+
+  #[test]
+  fn test_weakref_dies_with_the_last_reference() {
+    let (interpreter, engine) = interpret(
+      "class Point {} var p = Point(); var w = weakref(p);
+      p = nil; var dead = is_dead(w); var target = w.deref();",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "dead"), "true");
+    assert_eq!(global(&interpreter, "target"), "nil");
+  }
+
+  #[test]
+  fn test_weakref_to_a_local_dies_when_the_function_returns() {
+    let (interpreter, engine) = interpret(
+      "class Point {} fun make() { var p = Point(); return weakref(p); }
+      var w = make(); var dead = is_dead(w);",
+    );
+
+    assert!(!engine.has_errors());
+    assert_eq!(global(&interpreter, "dead"), "true");
+  }
+
+  #[test]
+  fn test_weakref_needs_an_instance() {
+    let (interpreter, engine) = interpret("var w = weakref([1]);");
+
+    let diagnostic = &engine.get_diagnostics()[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::RuntimeError);
+    assert_eq!(diagnostic.message, "weakref expects an instance, got [1]");
+    assert!(interpreter.env.borrow().get("w").is_none());
+  }
+}
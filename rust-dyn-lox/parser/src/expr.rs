@@ -15,6 +15,11 @@ pub enum Expr {
     operator: Token,
     rhs: Box<Expr>,
   },
+  Logical {
+    lhs: Box<Expr>,
+    operator: Token, // and, or, && or ||, the rhs only runs when the lhs doesn't decide
+    rhs: Box<Expr>,
+  },
   Assign {
     name: Token, // must be IDENTIFIER
     value: Box<Expr>,
@@ -30,6 +35,14 @@ pub enum Expr {
     arguments: Vec<Expr>,
   },
   Grouping(Box<Expr>),
+  Array {
+    bracket: Token, // the opening '['
+    elements: Vec<Expr>,
+  },
+  Map {
+    brace: Token, // the opening '{'
+    entries: Vec<(Expr, Expr)>,
+  },
   Get {
     object: Box<Expr>,
     name: Token,
@@ -41,6 +54,10 @@ pub enum Expr {
   },
   This(Token),
   Super(Token, Token),
+  Template {
+    backtick: Token,  // the head segment
+    parts: Vec<Expr>, // string segments and interpolated exprs, in source order
+  },
 }
 
 impl fmt::Display for Expr {
@@ -50,7 +67,24 @@ impl fmt::Display for Expr {
       Expr::Identifier(token) => write!(f, "{}", token.lexeme),
       Expr::Unary { operator, rhs } => write!(f, "({} {})", operator.lexeme, rhs),
       Expr::Binary { lhs, operator, rhs } => write!(f, "⚙️ ({} {} {})", lhs, operator.lexeme, rhs),
+      Expr::Logical { lhs, operator, rhs } => write!(f, "({} {} {})", lhs, operator.lexeme, rhs),
       Expr::Grouping(expr) => write!(f, "({})", expr),
+      Expr::Array { elements, .. } => {
+        let elements = elements
+          .iter()
+          .map(|e| format!("{}", e))
+          .collect::<Vec<_>>()
+          .join(", ");
+        write!(f, "[{}]", elements)
+      },
+      Expr::Map { entries, .. } => {
+        let entries = entries
+          .iter()
+          .map(|(k, v)| format!("{}: {}", k, v))
+          .collect::<Vec<_>>()
+          .join(", ");
+        write!(f, "{{{}}}", entries)
+      },
       Expr::Assign { name, value } => write!(f, "({} = {})", name.lexeme, value),
       Expr::Ternary {
         condition,
@@ -78,12 +112,22 @@ impl fmt::Display for Expr {
       } => {
         write!(f, "({}.{} = {})", object, name.lexeme, value)
       },
-      Expr::This(token) => {
+      Expr::This(_token) => {
         write!(f, "this")
       },
-      Expr::Super(token, name) => {
+      Expr::Super(_token, name) => {
         write!(f, "super.{}", name.lexeme)
       },
+      Expr::Template { parts, .. } => {
+        let parts = parts
+          .iter()
+          .map(|part| match part {
+            Expr::Literal(segment) => segment.lexeme.to_string(),
+            expr => format!("${{{}}}", expr),
+          })
+          .collect::<String>();
+        write!(f, "`{}`", parts)
+      },
     }
   }
 }
@@ -110,6 +154,13 @@ impl Expr {
         rhs.build_tree(&new_prefix, true);
       },
 
+      Expr::Logical { lhs, operator, rhs } => {
+        println!("{}{}Logical({})", prefix, connector, operator.lexeme);
+        let new_prefix = format!("{}{}", prefix, extension);
+        lhs.build_tree(&new_prefix, false);
+        rhs.build_tree(&new_prefix, true);
+      },
+
       Expr::Unary { operator, rhs } => {
         println!("{}{}Unary({})", prefix, connector, operator.lexeme);
         rhs.build_tree(&format!("{}{}", prefix, extension), true);
@@ -120,6 +171,24 @@ impl Expr {
         expr.build_tree(&format!("{}{}", prefix, extension), true);
       },
 
+      Expr::Array { elements, .. } => {
+        println!("{}{}Array", prefix, connector);
+        let new_prefix = format!("{}{}", prefix, extension);
+        for (i, element) in elements.iter().enumerate() {
+          element.build_tree(&new_prefix, i == elements.len() - 1);
+        }
+      },
+
+      Expr::Map { entries, .. } => {
+        println!("{}{}Map", prefix, connector);
+        let new_prefix = format!("{}{}", prefix, extension);
+        for (i, (key, value)) in entries.iter().enumerate() {
+          let is_last = i == entries.len() - 1;
+          key.build_tree(&new_prefix, false);
+          value.build_tree(&new_prefix, is_last);
+        }
+      },
+
       Expr::Assign { name, value } => {
         println!("{}{}Assign({})", prefix, connector, name.lexeme);
         value.build_tree(&format!("{}{}", prefix, extension), true);
@@ -183,12 +252,19 @@ impl Expr {
         println!("{}└── value:", new_prefix);
         value.build_tree(&format!("{}    ", new_prefix), true);
       },
-      Expr::This(token) => {
+      Expr::This(_token) => {
         println!("{}{}This", prefix, connector);
       },
-      Expr::Super(token, name) => {
+      Expr::Super(_token, _name) => {
         println!("{}{}Super", prefix, connector);
       },
+      Expr::Template { parts, .. } => {
+        println!("{}{}Template", prefix, connector);
+        let new_prefix = format!("{}{}", prefix, extension);
+        for (i, part) in parts.iter().enumerate() {
+          part.build_tree(&new_prefix, i == parts.len() - 1);
+        }
+      },
     }
   }
 }
@@ -53,9 +53,9 @@
 *
 * ternary        → logical_or ( "?" expr ":" ternary )? ;
 *
-* logical_or     → logical_and ( "or" logical_and )* ;
+* logical_or     → logical_and ( ( "or" | "||" ) logical_and )* ;
 *
-* logical_and    → equality ( "and" equality )* ;
+* logical_and    → equality ( ( "and" | "&&" ) equality )* ;
 *
 * equality       → comparison ( ( "!=" | "==" ) comparison )* ;
 *
@@ -72,9 +72,10 @@
 *
 * arguments      → expr ( "," expr )* ;
 *
-* primary        → NUMBER | STRING | IDENTIFIER
+* primary        → "-"? NUMBER | STRING | IDENTIFIER
 *                | "true" | "false" | "nil" | "this" | ( "super" "." IDENTIFIER )
-*                | "(" expr ")" ;
+*                | "(" expr ")" | "[" arguments? "]"
+*                | "{" ( expr ":" expr ( "," expr ":" expr )* )? "}" ;
 *
 */
 
@@ -84,6 +85,7 @@ use diagnostic::{
   DiagnosticEngine,
 };
 use scanner::token::{
+  stream::TokenStream,
   types::{Literal, TokenType},
   Token,
 };
@@ -94,20 +96,17 @@ pub mod expr;
 pub mod stmt;
 
 pub struct Parser {
-  /// The tokens preduced by the scanner
-  pub tokens: Vec<Token>,
-  /// The pointer to the current token we are looking at
-  pub current: usize,
+  /// The tokens preduced by the scanner, along with the cursor to the current token
+  pub tokens: TokenStream,
   /// List of exprs
   pub ast: Vec<Stmt>,
 }
 
 impl Parser {
   /// Function to init a new struct
-  pub fn new(tokens: Vec<Token>) -> Self {
+  pub fn new(tokens: TokenStream) -> Self {
     Self {
       tokens,
-      current: 0,
       ast: Vec::new(),
     }
   }
@@ -115,14 +114,29 @@ impl Parser {
   pub fn parse(&mut self, engine: &mut DiagnosticEngine) {
     while !self.is_eof() {
       match self.parse_program(engine) {
-        Ok(stmt) => {
-          stmt.print_tree();
-          self.ast.push(stmt);
-        },
+        Ok(stmt) => self.ast.push(stmt),
         Err(_) => self.synchronize(),
       }
     }
   }
+
+  /// Function that parses the tokens as a single expression, without the trailing `;`, the
+  /// expression must use every token.
+  pub fn parse_expression(&mut self, engine: &mut DiagnosticEngine) -> Option<Expr> {
+    if self.is_eof() {
+      self.error_eof(engine);
+      return None;
+    }
+
+    let expr = self.parse_expr(engine).ok()?;
+    if !self.is_eof() {
+      // Reports the first token left over.
+      let _ = self.expect(TokenType::Eof, engine);
+      return None;
+    }
+
+    Some(expr)
+  }
 }
 
 impl Parser {
@@ -211,8 +225,7 @@ impl Parser {
     let params = if matches!(self.current_token().token_type, TokenType::RightParen) {
       vec![]
     } else {
-      let params = self.parse_parameters(engine)?;
-      params
+      self.parse_parameters(engine)?
     };
 
     self.advance(); // consume the ")"
@@ -226,9 +239,17 @@ impl Parser {
         Ok(Stmt::Fun(
           Expr::Identifier(Token {
             token_type: TokenType::Identifier,
-            lexeme: uuid.to_string().split_once('-').unwrap().0.to_string(),
+            lexeme: uuid
+              .to_string()
+              .split_once('-')
+              .unwrap()
+              .0
+              .to_string()
+              .into(),
             literal: Literal::Nil,
             position: (0, 0),
+            span_start: 0,
+            span_end: 0,
           }),
           params,
           Box::new(body),
@@ -244,7 +265,7 @@ impl Parser {
       let check = matches!(expr, Expr::Identifier(_));
 
       if !check {
-        let token = parser.tokens[parser.current - 2].clone();
+        let token = parser.tokens.look_back(2).unwrap().clone();
         let diag = Diagnostic::new(
           DiagnosticCode::UnexpectedToken,
           "Unexpected parameter".to_string(),
@@ -339,7 +360,8 @@ impl Parser {
     self.expect(TokenType::Return, engine)?;
 
     if matches!(self.current_token().token_type, TokenType::SemiColon) {
-      return Ok(Stmt::Return(self.current_token(), None));
+      self.advance(); // consume ;
+      return Ok(Stmt::Return(token, None));
     }
 
     let value = self.parse_expr(engine)?;
@@ -367,9 +389,7 @@ impl Parser {
 
     // Check for identifier
     if !matches!(self.current_token().token_type, TokenType::Identifier) {
-      let mut span = self.current_token().to_span();
-      span.line += 1;
-      span.column -= 1;
+      let span = self.current_token().to_span();
       let diagnostic = Diagnostic::new(
         DiagnosticCode::ExpectedIdentifier,
         "Expected identifier after 'var'".to_string(),
@@ -396,7 +416,7 @@ impl Parser {
 
     if matches!(self.current_token().token_type, TokenType::SemiColon) {
       self.advance(); // consume ;
-      return Ok(Stmt::VarDecl(identifier, None));
+      Ok(Stmt::VarDecl(identifier, None))
     } else if matches!(self.current_token().token_type, TokenType::Equal) {
       self.advance(); // consume =
                       // TODO: parse the caller in the declaration
@@ -405,7 +425,10 @@ impl Parser {
       let expr;
 
       if matches!(self.current_token().token_type, TokenType::Identifier)
-        && self.tokens[self.current + 1].token_type == TokenType::LeftParen
+        && self
+          .tokens
+          .peek_next()
+          .is_some_and(|token| token.token_type == TokenType::LeftParen)
       {
         let callee = self.parse_call(engine)?;
         expr = callee;
@@ -427,7 +450,7 @@ impl Parser {
         if !is_function {
           self.advance(); // consume ;
         }
-        return Ok(Stmt::VarDecl(identifier, Some(expr)));
+        Ok(Stmt::VarDecl(identifier, Some(expr)))
       } else {
         // Missing semicolon diagnostic
         let diagnostic = Diagnostic::new(
@@ -440,15 +463,14 @@ impl Parser {
         ));
 
         engine.emit(diagnostic);
-        return Err(());
+        Err(())
       }
     } else {
       // Expected = or ;
       let token = self.current_token();
       let mut span = token.to_span();
       span.length = 1;
-      span.column = identifier.position.1;
-      let len = identifier.lexeme.len();
+      span.column = identifier.position.1 + identifier.lexeme.chars().count();
       let diagnostic = Diagnostic::new(
         DiagnosticCode::UnexpectedToken,
         format!(
@@ -461,17 +483,12 @@ impl Parser {
         Some("expected '=' or ';' here".to_string()),
       ))
       .with_label(Label::secondary(
-        Span {
-          length: len,
-          column: identifier.position.1 - len,
-          ..token.to_span()
-        },
-        // identifier.to_span(),
+        identifier.to_span(),
         Some("variable declared here".to_string()),
       ));
 
       engine.emit(diagnostic);
-      return Err(());
+      Err(())
     }
   }
 
@@ -523,6 +540,8 @@ impl Parser {
       "true".to_string(),
       Literal::Boolean,
       (0, 0),
+      0,
+      0,
     )));
     body = Stmt::While(Box::new(condition_expr), Box::new(body));
 
@@ -595,7 +614,7 @@ impl Parser {
 
   // Helper method to check if current token matches a type
   fn matches_token(&self, token_type: TokenType) -> bool {
-    !self.is_eof() && self.tokens[self.current].token_type == token_type
+    !self.is_eof() && self.tokens.check(token_type)
   }
 }
 
@@ -636,13 +655,14 @@ impl Parser {
     let lhs = self.parse_ternary(engine)?;
 
     if !self.is_eof() && matches!(self.current_token().token_type, TokenType::Equal) {
+      let equals = self.current_token();
       self.advance();
 
       let rhs = self.parse_assignment(engine)?;
 
       if let Expr::Identifier(name) = lhs {
         return Ok(Expr::Assign {
-          name: name,
+          name,
           value: Box::new(rhs),
         });
       } else if let Expr::Get { object, name } = lhs {
@@ -652,7 +672,19 @@ impl Parser {
           value: Box::new(rhs),
         });
       } else {
-        self.error_unexpected_token(engine, "in assignment, left side must be an identifier");
+        let diagnostic = Diagnostic::new(
+          DiagnosticCode::InvalidAssignmentTarget,
+          "Invalid assignment target".to_string(),
+        )
+        .with_label(Label::primary(
+          equals.to_span(),
+          Some("can't assign to the expression before this '='".to_string()),
+        ))
+        .with_help(
+          "Only variables and properties (a = 1, obj.field = 1) can be assigned".to_string(),
+        );
+
+        engine.emit(diagnostic);
         return Err(());
       }
     }
@@ -714,9 +746,9 @@ impl Parser {
 
     while !self.is_eof() && matches!(self.current_token().token_type, TokenType::Or) {
       let token = self.current_token();
-      self.advance(); // consume the &&
+      self.advance(); // consume the ||
       let rhs = self.parse_logic_and(engine)?;
-      lhs = Expr::Binary {
+      lhs = Expr::Logical {
         lhs: Box::new(lhs),
         operator: token,
         rhs: Box::new(rhs),
@@ -733,7 +765,7 @@ impl Parser {
       let token = self.current_token();
       self.advance(); // consume the &&
       let rhs = self.parse_equality(engine)?;
-      lhs = Expr::Binary {
+      lhs = Expr::Logical {
         lhs: Box::new(lhs),
         operator: token,
         rhs: Box::new(rhs),
@@ -848,19 +880,21 @@ impl Parser {
   }
 
   /// Parse unary: ( "!" | "-" ) unary | call
-
   fn parse_unary(&mut self, engine: &mut DiagnosticEngine) -> Result<Expr, ()> {
     let token = self.current_token();
 
     match token.token_type {
+      // `-1` is a negative literal, left for `parse_primary`. A number with a postfix keeps the
+      // unary minus, it binds looser than the call or property access.
+      TokenType::Minus if self.next_is_bare_number() => self.parse_call(engine),
       TokenType::Bang | TokenType::Minus => {
         self.advance();
         let rhs = self.parse_unary(engine)?;
 
-        return Ok(Expr::Unary {
+        Ok(Expr::Unary {
           operator: token,
           rhs: Box::new(rhs),
-        });
+        })
       },
       _ => self.parse_call(engine), // Changed from parse_primary
     }
@@ -924,7 +958,7 @@ impl Parser {
 
           expr = Expr::Get {
             object: Box::new(expr),
-            name: name,
+            name,
           };
         },
         _ => break, // No more calls
@@ -986,12 +1020,56 @@ impl Parser {
       | TokenType::False
       | TokenType::Nil => {
         self.advance();
-        return Ok(Expr::Literal(token));
+        Ok(Expr::Literal(token))
+      },
+
+      TokenType::Minus if self.next_is_bare_number() => {
+        self.advance();
+        let number = self.current_token();
+        self.advance();
+
+        Ok(Expr::Literal(Token::new(
+          TokenType::Number,
+          format!("-{}", number.lexeme),
+          Literal::Number,
+          token.position,
+          token.span_start,
+          number.span_end,
+        )))
       },
 
       TokenType::This => {
         self.advance();
-        return Ok(Expr::This(token));
+        Ok(Expr::This(token))
+      },
+
+      TokenType::TemplateHead => {
+        self.advance(); // consume the head
+
+        let mut parts = vec![Expr::Literal(token.clone())];
+        loop {
+          self.expect(TokenType::InterpolationStart, engine)?;
+          parts.push(self.parse_expr(engine)?);
+          self.expect(TokenType::InterpolationEnd, engine)?;
+
+          let segment = self.current_token();
+          match segment.token_type {
+            TokenType::TemplateMiddle => {
+              self.advance();
+              parts.push(Expr::Literal(segment));
+            },
+            _ => {
+              let tail = self.expect(TokenType::TemplateTail, engine)?;
+              parts.push(Expr::Literal(tail));
+              break;
+            },
+          }
+        }
+
+        Ok(Expr::Template {
+          backtick: token,
+          parts,
+        })
       },
 
       TokenType::Super => {
@@ -999,14 +1077,14 @@ impl Parser {
         self.expect(TokenType::Dot, engine)?;
         let method = self.parse_primary(engine)?;
         if let Expr::Identifier(name) = method {
-          return Ok(Expr::Super(token, name));
+          Ok(Expr::Super(token, name))
         } else {
-          return Err(());
+          Err(())
         }
       },
       TokenType::Identifier => {
         self.advance();
-        return Ok(Expr::Identifier(token));
+        Ok(Expr::Identifier(token))
       },
 
       TokenType::LeftParen => {
@@ -1020,13 +1098,12 @@ impl Parser {
 
           // For EOF, use the PREVIOUS token's end position
           let error_span = if self.is_eof() {
-            let prev_token = &self.tokens[self.current - 1];
+            let prev_token = self.tokens.previous().unwrap_or(&current);
             Span {
-              // TODO: add the real file name
-              file: "asdfa".to_string(),
-              line: prev_token.position.0,
-              column: prev_token.position.1 + prev_token.lexeme.len(),
+              column: prev_token.position.1 + prev_token.lexeme.chars().count(),
               length: 1,
+              byte_offset: prev_token.span_end,
+              ..prev_token.to_span()
             }
           } else {
             current.to_span()
@@ -1050,7 +1127,46 @@ impl Parser {
         }
 
         self.advance(); // consume ')'
-        return Ok(Expr::Grouping(Box::new(expr)));
+        Ok(Expr::Grouping(Box::new(expr)))
+      },
+
+      TokenType::LeftBracket => {
+        self.advance(); // consume '['
+
+        let mut elements = Vec::new();
+        if !self.matches_token(TokenType::RightBracket) {
+          elements = self.parse_arguments(engine)?;
+        }
+        self.expect(TokenType::RightBracket, engine)?;
+
+        Ok(Expr::Array {
+          bracket: token,
+          elements,
+        })
+      },
+
+      // Blocks are statements, so a brace in expression position opens a map.
+      TokenType::LeftBrace => {
+        self.advance(); // consume '{'
+
+        let mut entries = Vec::new();
+        while !self.is_eof() && !self.matches_token(TokenType::RightBrace) {
+          let key = self.parse_assignment(engine)?;
+          self.expect(TokenType::Colon, engine)?;
+          let value = self.parse_assignment(engine)?;
+          entries.push((key, value));
+
+          if !self.matches_token(TokenType::Comma) {
+            break;
+          }
+          self.advance(); // consume ','
+        }
+        self.expect(TokenType::RightBrace, engine)?;
+
+        Ok(Expr::Map {
+          brace: token,
+          entries,
+        })
       },
 
       TokenType::SemiColon => {
@@ -1069,15 +1185,14 @@ impl Parser {
 
         self.ast.push(fun);
         if let Expr::Identifier(name) = token {
-          return Ok(Expr::Identifier(name));
+          Ok(Expr::Identifier(name))
         } else {
-          return Err(());
+          Err(())
         }
       },
 
       _ => {
-        let mut token = self.current_token();
-        token.position.1 = 0;
+        let token = self.current_token();
         let diagnostic = Diagnostic::new(
           DiagnosticCode::ExpectedExpression,
           "Expected expr".to_string(),
@@ -1088,26 +1203,28 @@ impl Parser {
         ));
         engine.emit(diagnostic);
 
-        return Err(());
+        Err(())
       },
     }
   }
 
   ///  Function that moves the pointer one step
   fn advance(&mut self) {
-    if !self.is_eof() {
-      self.current += 1;
-    }
+    self.tokens.advance();
   }
 
   /// Function that gets the currnete token
   fn current_token(&mut self) -> Token {
-    self.tokens[self.current].clone()
+    self
+      .tokens
+      .peek()
+      .expect("token stream ends with EOF")
+      .clone()
   }
 
   /// Function that returns bool indicating the EOF state
   fn is_eof(&self) -> bool {
-    self.current == (self.tokens.len() - 1)
+    self.tokens.is_at_end()
   }
 
   /// Function that consume the code until there's valid tokens to start a new expr
@@ -1128,9 +1245,7 @@ impl Parser {
 
   fn check_double_semicolon(&mut self, engine: &mut DiagnosticEngine) {
     if !self.is_eof() && matches!(self.current_token().token_type, TokenType::SemiColon) {
-      let mut token = self.current_token();
-      token.position.0 += 1;
-      token.position.1 -= 1;
+      let token = self.current_token();
 
       let diagnostic = Diagnostic::new(
         DiagnosticCode::UnexpectedToken,
@@ -1145,45 +1260,39 @@ impl Parser {
     }
   }
 
-  fn error_unexpected_token(&mut self, engine: &mut DiagnosticEngine, context: &str) {
-    let mut token = self.current_token();
-    token.position.0 += 1;
-    token.position.1 -= 1;
-    let diagnostic = Diagnostic::new(
-      DiagnosticCode::UnexpectedToken,
-      format!("Unexpected token '{}' {}", token.lexeme, context),
-    )
-    .with_label(Label::primary(
-      token.to_span(),
-      Some("unexpected token found here".to_string()),
-    ));
-
-    engine.emit(diagnostic);
-  }
-
   fn error_eof(&mut self, engine: &mut DiagnosticEngine) {
     let token = self.current_token();
-    let diagnostic = Diagnostic::new(
-      DiagnosticCode::UnexpectedEof,
-      "Unexpected end of file".to_string(),
-    )
-    .with_label(Label::primary(
-      token.to_span(),
-      Some("expected some expr".to_string()),
-    ));
+    let diagnostic = Diagnostic::from_code(DiagnosticCode::UnexpectedEof).with_label(
+      Label::primary(token.to_span(), Some("expected some expr".to_string())),
+    );
 
     engine.emit(diagnostic);
   }
 
+  /// Function that returns `true` when the token after the current one is a number that no
+  /// call or property access follows, so `-123.abs()` stays `-(123.abs())` like `-n.abs()`.
+  fn next_is_bare_number(&self) -> bool {
+    self
+      .tokens
+      .peek_next()
+      .is_some_and(|token| token.token_type == TokenType::Number)
+      && !self
+        .tokens
+        .peek_ahead(2)
+        .is_some_and(|token| matches!(token.token_type, TokenType::Dot | TokenType::LeftParen))
+  }
+
   fn span_prev(&mut self) -> Span {
-    if self.current > 0 {
-      let token = self.current_token();
-      let mut prev_token = self.tokens[self.current - 1].clone();
-      prev_token.position.0 = token.position.0;
-      prev_token.lexeme = token.lexeme;
-      prev_token.to_span()
-    } else {
-      self.tokens[0].to_span()
+    let token = self.current_token();
+
+    match self.tokens.previous() {
+      Some(prev_token) => {
+        let mut prev_token = prev_token.clone();
+        prev_token.position.0 = token.position.0;
+        prev_token.lexeme = token.lexeme;
+        prev_token.to_span()
+      },
+      None => token.to_span(),
     }
   }
 }
@@ -1210,25 +1319,23 @@ impl Parser {
   /// Error for when we expect a token but hit EOF
   fn error_expected_token_eof(&mut self, expected: TokenType, engine: &mut DiagnosticEngine) {
     let token = self.current_token();
-    let last_token = &self.tokens[self.current - 1];
+    let last_token = self.tokens.previous().unwrap_or(&token);
 
     let error_span = Span {
       file: last_token.to_span().file.clone(),
       line: token.position.0,
       column: token.position.1,
       length: 1,
+      byte_offset: token.span_start,
     };
 
     let diagnostic = Diagnostic::new(
       DiagnosticCode::UnexpectedEof,
-      format!(
-        "Expected '{}', but reached end of file",
-        expected.to_string()
-      ),
+      format!("Expected '{}', but reached end of file", expected),
     )
     .with_label(Label::primary(
       error_span,
-      Some(format!("expected '{}' here", expected.to_string())),
+      Some(format!("expected '{}' here", expected)),
     ))
     .with_label(Label::secondary(
       last_token.to_span(),
@@ -1257,7 +1364,6 @@ impl Parser {
     engine.emit(diagnostic);
   }
 }
-/// Helper function to convert TokenType to a readable string
 
 /// Provides contextual help based on what was expected vs found
 fn get_token_help(expected: &TokenType, found: &Token) -> String {
@@ -71,7 +71,7 @@ impl fmt::Display for Stmt {
       Stmt::Continue(token) => {
         write!(f, "Continue({})", token.lexeme)
       },
-      Stmt::Class(name, superclass, stmts, static_methods) => {
+      Stmt::Class(name, _superclass, _stmts, _static_methods) => {
         write!(f, "Class({}, [...])", name)
       },
     }
@@ -153,7 +153,7 @@ impl Stmt {
         let params_str = params
           .iter()
           .map(|p| match p {
-            Expr::Identifier(t) => t.lexeme.clone(),
+            Expr::Identifier(t) => t.lexeme.to_string(),
             _ => format!("{}", p),
           })
           .collect::<Vec<_>>()
@@ -182,7 +182,7 @@ impl Stmt {
         println!("{}{}Continue", prefix, connector);
       },
 
-      Stmt::Class(name, superclass, methods, static_methods) => {
+      Stmt::Class(name, _superclass, methods, _static_methods) => {
         println!("{}{}Class({})", prefix, connector, name);
         let new_prefix = format!("{}{}", prefix, extension);
         for (i, method) in methods.iter().enumerate() {
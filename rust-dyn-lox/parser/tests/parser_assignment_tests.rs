@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::{diagnostic_code::DiagnosticCode, DiagnosticEngine};
+  use parser::Parser;
+  use scanner::Scanner;
+
+  fn parse(source: &str) -> DiagnosticEngine {
+    let mut engine = DiagnosticEngine::new();
+    let (tokens, _) = Scanner::new(source.to_string()).scan_tokens().unwrap();
+
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut engine);
+    engine
+  }
+
+  fn codes(engine: &DiagnosticEngine) -> Vec<DiagnosticCode> {
+    engine
+      .get_diagnostics()
+      .iter()
+      .map(|diagnostic| diagnostic.code)
+      .collect()
+  }
+
+  #[test]
+  fn test_invalid_assignment_targets() {
+    for source in ["1 = 2;", "(a + b) = c;", "f() = val;"] {
+      let engine = parse(source);
+
+      assert_eq!(
+        codes(&engine),
+        vec![DiagnosticCode::InvalidAssignmentTarget],
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn test_valid_assignment_targets() {
+    for source in ["a = 1;", "obj.field = 1;", "a = b = 2;"] {
+      let engine = parse(source);
+
+      assert!(!engine.has_errors(), "{}", source);
+    }
+  }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::DiagnosticEngine;
+  use parser::{expr::Expr, stmt::Stmt, Parser};
+  use scanner::{token::types::TokenType, Scanner};
+
+  fn parse(source: &str) -> Vec<Stmt> {
+    let mut engine = DiagnosticEngine::new();
+    let (tokens, _) = Scanner::new(source.to_string()).scan_tokens().unwrap();
+
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut engine);
+    assert!(!engine.has_errors(), "{}", source);
+    parser.ast
+  }
+
+  #[test]
+  fn test_for_desugars_to_while() {
+    let ast = parse("for (var i = 0; i < 3; i = i + 1) print(i);");
+
+    // `{ init; while (cond) { body; incr; } }`
+    let [Stmt::Block(outer)] = ast.as_slice() else {
+      panic!("expected a block, got {:?}", ast);
+    };
+    let [Stmt::VarDecl(name, Some(_)), Stmt::While(condition, body)] = outer.as_slice() else {
+      panic!("expected the initializer then a while, got {:?}", outer);
+    };
+    assert_eq!(name.lexeme, "i");
+    assert!(matches!(condition.as_ref(), Expr::Binary { .. }));
+
+    let Stmt::Block(inner) = body.as_ref() else {
+      panic!("expected the body and increment in a block, got {:?}", body);
+    };
+    assert!(matches!(
+      inner.as_slice(),
+      [
+        Stmt::Expr(Expr::Call { .. }),
+        Stmt::Expr(Expr::Assign { .. })
+      ]
+    ));
+  }
+
+  #[test]
+  fn test_for_without_clauses_loops_on_true() {
+    let ast = parse("for (;;) break;");
+
+    let [Stmt::While(condition, body)] = ast.as_slice() else {
+      panic!("expected a bare while, got {:?}", ast);
+    };
+    assert!(
+      matches!(condition.as_ref(), Expr::Literal(token) if token.token_type == TokenType::True)
+    );
+    assert!(matches!(body.as_ref(), Stmt::Break(_)));
+  }
+
+  #[test]
+  fn test_else_if_nests_in_the_else_branch() {
+    let ast = parse("if (a) x = 1; else if (b) x = 2; else if (c) x = 3; else x = 4;");
+
+    // Each `else if` is an `If` sitting in the else branch of the one before it.
+    let mut stmt = &ast[0];
+    for name in ["a", "b", "c"] {
+      let Stmt::If(condition, _, Some(else_branch)) = stmt else {
+        panic!("expected an if with an else, got {:?}", stmt);
+      };
+      assert!(matches!(condition.as_ref(), Expr::Identifier(token) if token.lexeme == name));
+      stmt = else_branch;
+    }
+    assert!(matches!(stmt, Stmt::Expr(Expr::Assign { .. })));
+  }
+}
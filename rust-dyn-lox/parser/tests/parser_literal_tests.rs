@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::DiagnosticEngine;
+  use parser::{expr::Expr, stmt::Stmt, Parser};
+  use scanner::Scanner;
+
+  fn parse_expr(source: &str) -> Expr {
+    let mut engine = DiagnosticEngine::new();
+    let (tokens, _) = Scanner::new(source.to_string()).scan_tokens().unwrap();
+
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut engine);
+    assert!(!engine.has_errors(), "{}", source);
+
+    match parser.ast.remove(0) {
+      Stmt::Expr(expr) => expr,
+      stmt => panic!("expected an expression statement, found {:?}", stmt),
+    }
+  }
+
+  #[test]
+  fn test_minus_number_is_a_negative_literal() {
+    assert!(matches!(parse_expr("-3.5;"), Expr::Literal(token) if token.lexeme == "-3.5"));
+    assert!(matches!(
+      parse_expr("-1 + 2;"),
+      Expr::Binary { lhs, .. } if matches!(*lhs, Expr::Literal(_))
+    ));
+  }
+
+  #[test]
+  fn test_minus_on_other_exprs_stays_unary() {
+    assert!(matches!(parse_expr("-(2);"), Expr::Unary { .. }));
+    assert!(matches!(parse_expr("-a;"), Expr::Unary { .. }));
+    assert!(matches!(
+      parse_expr("- -1;"),
+      Expr::Unary { rhs, .. } if matches!(*rhs, Expr::Literal(_))
+    ));
+    // A binary minus is still an operator.
+    assert!(matches!(parse_expr("3 -1;"), Expr::Binary { .. }));
+  }
+
+  #[test]
+  fn test_minus_before_a_number_postfix_stays_unary() {
+    for source in ["-123.abs();", "-123.to_string;", "-1(2);"] {
+      assert!(
+        matches!(
+          parse_expr(source),
+          Expr::Unary { rhs, .. } if !matches!(*rhs, Expr::Literal(_))
+        ),
+        "{}",
+        source
+      );
+    }
+  }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::DiagnosticEngine;
+  use parser::{expr::Expr, stmt::Stmt, Parser};
+  use scanner::Scanner;
+
+  fn parse_expr(source: &str) -> Expr {
+    let mut engine = DiagnosticEngine::new();
+    let (tokens, _) = Scanner::new(source.to_string()).scan_tokens().unwrap();
+
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut engine);
+    assert!(!engine.has_errors(), "{}", source);
+
+    match parser.ast.remove(0) {
+      Stmt::Expr(expr) => expr,
+      stmt => panic!("expected an expression statement, found {:?}", stmt),
+    }
+  }
+
+  #[test]
+  fn test_logical_operators_are_logical_nodes() {
+    for (source, lexeme) in [
+      ("a and b;", "and"),
+      ("a or b;", "or"),
+      ("a && b;", "&&"),
+      ("a || b;", "||"),
+    ] {
+      assert!(
+        matches!(
+          parse_expr(source),
+          Expr::Logical { operator, .. } if operator.lexeme == lexeme
+        ),
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn test_and_binds_tighter_than_or() {
+    let Expr::Logical { lhs, operator, rhs } = parse_expr("a == b or c and d;") else {
+      panic!("expected a logical expression");
+    };
+
+    assert_eq!(operator.lexeme, "or");
+    assert!(matches!(*lhs, Expr::Binary { .. }));
+    assert!(matches!(*rhs, Expr::Logical { operator, .. } if operator.lexeme == "and"));
+  }
+}
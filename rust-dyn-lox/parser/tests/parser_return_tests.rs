@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::DiagnosticEngine;
+  use parser::{stmt::Stmt, Parser};
+  use scanner::{token::types::TokenType, Scanner};
+
+  fn parse(source: &str) -> (Vec<Stmt>, DiagnosticEngine) {
+    let mut engine = DiagnosticEngine::new();
+    let (tokens, _) = Scanner::new(source.to_string()).scan_tokens().unwrap();
+
+    let mut parser = Parser::new(tokens);
+    parser.parse(&mut engine);
+    (parser.ast, engine)
+  }
+
+  #[test]
+  fn test_bare_return_consumes_its_semicolon() {
+    let (ast, engine) = parse("fun f() { return; } f();");
+
+    assert!(!engine.has_errors());
+    assert_eq!(ast.len(), 2);
+
+    let Stmt::Fun(_, _, body) = &ast[0] else {
+      panic!("expected a function, found {:?}", ast[0]);
+    };
+    let Stmt::Block(body) = body.as_ref() else {
+      panic!("expected a block, found {:?}", body);
+    };
+    assert!(matches!(
+      body.as_slice(),
+      [Stmt::Return(token, None)] if token.token_type == TokenType::Return
+    ));
+  }
+
+  #[test]
+  fn test_return_with_a_value() {
+    let (ast, engine) = parse("fun f() { return 1; }");
+
+    assert!(!engine.has_errors());
+    let Stmt::Fun(_, _, body) = &ast[0] else {
+      panic!("expected a function, found {:?}", ast[0]);
+    };
+    assert!(matches!(
+      body.as_ref(),
+      Stmt::Block(body) if matches!(body.as_slice(), [Stmt::Return(_, Some(_))])
+    ));
+  }
+}
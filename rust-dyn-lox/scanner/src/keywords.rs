@@ -0,0 +1,25 @@
+use phf::phf_map;
+
+use crate::token::types::TokenType;
+
+/// Reserved words of the language, resolved through a perfect hash built at compile time so an
+/// identifier costs one hash and one comparison no matter how many keywords there are.
+pub static KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
+  "var" => TokenType::Var,
+  "fun" => TokenType::Fun,
+  "return" => TokenType::Return,
+  "if" => TokenType::If,
+  "else" => TokenType::Else,
+  "for" => TokenType::For,
+  "while" => TokenType::While,
+  "break" => TokenType::Break,
+  "continue" => TokenType::Continue,
+  "class" => TokenType::Class,
+  "this" => TokenType::This,
+  "true" => TokenType::True,
+  "false" => TokenType::False,
+  "nil" => TokenType::Nil,
+  "or" => TokenType::Or,
+  "and" => TokenType::And,
+  "super" => TokenType::Super,
+};
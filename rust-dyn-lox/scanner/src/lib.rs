@@ -1,33 +1,185 @@
-use crate::token::Token;
-use diagnostic::DiagnosticEngine;
+use std::{borrow::Cow, collections::HashMap};
 
+use crate::token::{stream::TokenStream, types::TokenType, Token};
+use diagnostic::{diagnostic::Diagnostic, diagnostic_code::Severity, DiagnosticEngine};
+
+pub mod keywords;
 pub mod token;
 mod utils;
 
-pub struct Scanner {
+/// Destination for the diagnostics found while scanning, so the scanner can run without a
+/// [`DiagnosticEngine`], e.g. from tests or tooling that only collects the errors. Warnings go
+/// through it too, their severity tells them apart.
+pub trait ScannerErrorSink {
+  fn report_error(&mut self, diagnostic: Diagnostic);
+}
+
+impl ScannerErrorSink for DiagnosticEngine {
+  fn report_error(&mut self, diagnostic: Diagnostic) {
+    self.emit(diagnostic);
+  }
+}
+
+impl ScannerErrorSink for Vec<Diagnostic> {
+  fn report_error(&mut self, diagnostic: Diagnostic) {
+    self.push(diagnostic);
+  }
+}
+
+/// Scanner over `source`, which is borrowed when the caller already owns the text so scanning
+/// doesn't copy it.
+pub struct Scanner<'a> {
   pub tokens: Vec<Token>,
-  pub source: String,
+  pub source: Cow<'a, str>,
   pub line: usize,
+  /// 1-based column of the next char to be scanned
   pub column: usize,
   pub current: usize,
   pub start: usize,
+  /// 1-based column where the token being scanned starts
+  pub start_column: usize,
+  /// Makes every `\n` outside strings and comments a `Newline` token, for parsers that end
+  /// statements at line breaks. Off by default
+  pub emit_newlines: bool,
+  /// Keeps comment tokens in the stream, set through [`Scanner::with_comments`]
+  emit_comments: bool,
+  /// Unescaped contents of the string being scanned, used as the lexeme of string tokens
+  string_value: String,
+  /// Every `${` interpolation that is still being scanned, innermost last
+  template_braces: Vec<OpenInterpolation>,
+  /// Set when a template segment stopped right before a `${`
+  interpolation_pending: bool,
+  /// Set when an interpolation just closed, so the next token continues the template string
+  template_resumes: bool,
+  /// Set once `next_token` has handed out the EOF token
+  reached_eof: bool,
+  /// Keywords added by the embedder, checked before the built-in `KEYWORDS`
+  keywords: HashMap<String, TokenType>,
+}
+
+/// A `${` interpolation the scanner is inside of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct OpenInterpolation {
+  /// Braces opened inside the interpolation and not closed yet
+  braces: usize,
+  /// Line, column and byte offset of the `${`
+  start: (usize, usize, usize),
+}
+
+/// Snapshot of the scanner's position, taken by [`Scanner::save_position`] so a speculative caller
+/// can rewind with [`Scanner::restore_position`]. It holds the template string state too, so a
+/// rewind across `${` or `}` leaves the scanner inside the same interpolations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannerCheckpoint {
+  line: usize,
+  column: usize,
+  current: usize,
+  start: usize,
+  start_column: usize,
+  reached_eof: bool,
+  string_value: String,
+  template_braces: Vec<OpenInterpolation>,
+  interpolation_pending: bool,
+  template_resumes: bool,
 }
 
-impl Scanner {
-  /// Function that created a new scanner
-  pub fn new(source: String) -> Self {
+impl<'a> Scanner<'a> {
+  /// Function that created a new scanner, takes either a `String` or a borrowed `&str`
+  pub fn new(source: impl Into<Cow<'a, str>>) -> Self {
     Self {
-      source,
-      column: 0,
-      line: 0,
+      source: source.into(),
+      column: 1,
+      line: 1,
       start: 0,
+      start_column: 1,
       current: 0,
+      emit_newlines: false,
       tokens: vec![],
+      emit_comments: false,
+      string_value: String::new(),
+      template_braces: vec![],
+      interpolation_pending: false,
+      template_resumes: false,
+      reached_eof: false,
+      keywords: HashMap::new(),
+    }
+  }
+
+  /// Function that keeps comment and doc comment tokens in the stream instead of dropping them,
+  /// for tools that read them back like a doc generator.
+  pub fn with_comments(mut self, emit_comments: bool) -> Self {
+    self.emit_comments = emit_comments;
+    self
+  }
+
+  /// Function that adds `keywords` on top of the built-in ones, an entry for a built-in word
+  /// overrides it, so mapping `"fun"` to `TokenType::Identifier` frees it up as a name.
+  pub fn with_keywords(mut self, keywords: HashMap<String, TokenType>) -> Self {
+    self.keywords.extend(keywords);
+    self
+  }
+
+  /// Funciton that scans the string buffer and reports any scanning errors into `errors`.
+  pub fn scan(&mut self, errors: &mut dyn ScannerErrorSink) {
+    self.get_tokens(errors);
+  }
+
+  /// Function that scans the string buffer without an outside sink, returning the tokens with the
+  /// warnings found while scanning or, once any error is found, every diagnostic found while
+  /// scanning. Warnings alone don't fail. The tokens are handed over rather than kept in `tokens`,
+  /// so a second call doesn't return the first scan again.
+  pub fn scan_tokens(&mut self) -> Result<(TokenStream, Vec<Diagnostic>), Vec<Diagnostic>> {
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+    self.get_tokens(&mut diagnostics);
+
+    let tokens = std::mem::take(&mut self.tokens);
+    if diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+      return Err(diagnostics);
+    }
+
+    Ok((TokenStream::new(tokens), diagnostics))
+  }
+
+  /// Function that iterates over the tokens scanned so far.
+  pub fn tokens_iter(&self) -> impl Iterator<Item = &Token> {
+    self.tokens.iter()
+  }
+
+  /// Function that consumes the scanner and gives back the tokens scanned so far.
+  pub fn into_tokens(self) -> Vec<Token> {
+    self.tokens
+  }
+
+  /// Function that saves where the scanner currently is.
+  pub fn save_position(&self) -> ScannerCheckpoint {
+    ScannerCheckpoint {
+      line: self.line,
+      column: self.column,
+      current: self.current,
+      start: self.start,
+      start_column: self.start_column,
+      reached_eof: self.reached_eof,
+      string_value: self.string_value.clone(),
+      template_braces: self.template_braces.clone(),
+      interpolation_pending: self.interpolation_pending,
+      template_resumes: self.template_resumes,
     }
   }
 
-  /// Funciton that scans the string buffer and returns tokens
-  pub fn scan(&mut self, engine: &mut DiagnosticEngine) {
-    self.get_tokens(engine);
+  /// Function that rewinds the scanner to a saved position, already pushed `tokens` are kept.
+  pub fn restore_position(&mut self, checkpoint: ScannerCheckpoint) {
+    self.line = checkpoint.line;
+    self.column = checkpoint.column;
+    self.current = checkpoint.current;
+    self.start = checkpoint.start;
+    self.start_column = checkpoint.start_column;
+    self.reached_eof = checkpoint.reached_eof;
+    self.string_value = checkpoint.string_value;
+    self.template_braces = checkpoint.template_braces;
+    self.interpolation_pending = checkpoint.interpolation_pending;
+    self.template_resumes = checkpoint.template_resumes;
   }
 }
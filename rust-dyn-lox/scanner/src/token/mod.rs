@@ -1,39 +1,72 @@
+use std::{borrow::Cow, fmt};
+
 use diagnostic::diagnostic::Span;
 
 use crate::token::types::{Literal, TokenType};
 
+pub mod stream;
 pub mod types;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Token {
   pub token_type: TokenType,
-  pub lexeme: String,
+  /// Borrowed for operators and keywords, owned when the text comes from the source.
+  pub lexeme: Cow<'static, str>,
   pub literal: Literal,
   pub position: (usize, usize),
+  /// Byte offset of the first char of the token in the source
+  pub span_start: usize,
+  /// Byte offset right past the last char of the token in the source
+  pub span_end: usize,
 }
 
 impl Token {
   pub fn new(
     token_type: TokenType,
-    lexeme: String,
+    lexeme: impl Into<Cow<'static, str>>,
     literal: Literal,
     position: (usize, usize),
+    span_start: usize,
+    span_end: usize,
   ) -> Self {
     Self {
       token_type,
-      lexeme,
+      lexeme: lexeme.into(),
       literal,
       position,
+      span_start,
+      span_end,
     }
   }
 
+  /// Function that returns `true` for the EOF token closing every stream.
+  pub fn is_eof(&self) -> bool {
+    self.token_type == TokenType::Eof
+  }
+
+  /// Function that checks the token carries a lexeme, only EOF, the empty string literal `""`, an
+  /// empty doc comment and empty template segments are allowed to have none.
+  pub fn validate(&self) -> bool {
+    !self.lexeme.is_empty()
+      || matches!(
+        self.token_type,
+        TokenType::Eof
+          | TokenType::String
+          | TokenType::DocComment
+          | TokenType::TemplateHead
+          | TokenType::TemplateMiddle
+          | TokenType::TemplateTail
+      )
+  }
+
   /// Function that takes a token and turn it to a span for the engine
   pub fn to_span(&self) -> Span {
     Span {
       file: "input.duck".to_string(),
       line: self.position.0,
       column: self.position.1,
-      length: self.lexeme.len(),
+      length: self.lexeme.chars().count(),
+      byte_offset: self.span_start,
     }
   }
 
@@ -42,7 +75,19 @@ impl Token {
       file: "input.duck".to_string(),
       line: token.position.0,
       column: token.position.1,
-      length: token.lexeme.len(),
+      length: token.lexeme.chars().count(),
+      byte_offset: token.span_start,
     }
   }
 }
+
+/// Shows the token as `Token(Plus, '+', line 3:7)` for error messages.
+impl fmt::Display for Token {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "Token({:?}, '{}', line {}:{})",
+      self.token_type, self.lexeme, self.position.0, self.position.1
+    )
+  }
+}
@@ -0,0 +1,106 @@
+use diagnostic::{
+  diagnostic::{Diagnostic, Label},
+  diagnostic_code::DiagnosticCode,
+};
+
+use crate::token::{types::TokenType, Token};
+
+/// Cursor over the tokens produced by the scanner, the stream always ends with the EOF token and
+/// the cursor never moves past it.
+#[derive(Debug, Clone, Default)]
+pub struct TokenStream {
+  tokens: Vec<Token>,
+  current: usize,
+}
+
+impl TokenStream {
+  /// Function that creates a new stream positioned at the first token
+  pub fn new(tokens: Vec<Token>) -> Self {
+    Self { tokens, current: 0 }
+  }
+
+  /// Function that returns the token under the cursor.
+  pub fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.current)
+  }
+
+  /// Function that returns the token right after the one under the cursor.
+  pub fn peek_next(&self) -> Option<&Token> {
+    self.peek_ahead(1)
+  }
+
+  /// Function that returns the token `distance` steps ahead of the cursor.
+  pub fn peek_ahead(&self, distance: usize) -> Option<&Token> {
+    self.tokens.get(self.current + distance)
+  }
+
+  /// Function that returns the last consumed token.
+  pub fn previous(&self) -> Option<&Token> {
+    self.look_back(1)
+  }
+
+  /// Function that returns the token `distance` steps behind the cursor.
+  pub fn look_back(&self, distance: usize) -> Option<&Token> {
+    self
+      .current
+      .checked_sub(distance)
+      .and_then(|index| self.tokens.get(index))
+  }
+
+  /// Function that consumes the token under the cursor and returns it, the EOF token is returned
+  /// again on every call once it is reached.
+  pub fn advance(&mut self) -> Option<Token> {
+    let token = self.peek().cloned();
+
+    if !self.is_at_end() {
+      self.current += 1;
+    }
+
+    token
+  }
+
+  /// Function that consumes the token under the cursor if it has the `expected` type, otherwise
+  /// the cursor stays put and a diagnostic pointing at the found token is returned.
+  pub fn expect(&mut self, expected: TokenType) -> Result<Token, Diagnostic> {
+    if self.check(expected.clone()) {
+      // `check` only passes on an existing token.
+      return Ok(self.advance().unwrap());
+    }
+
+    let Some(found) = self.peek() else {
+      return Err(Diagnostic::new(
+        DiagnosticCode::UnexpectedEof,
+        format!("Expected '{}', but reached end of file", expected),
+      ));
+    };
+
+    let code = if found.is_eof() {
+      DiagnosticCode::UnexpectedEof
+    } else {
+      DiagnosticCode::UnexpectedToken
+    };
+
+    Err(
+      Diagnostic::new(
+        code,
+        format!("Expected '{}', found '{}'", expected, found.lexeme),
+      )
+      .with_label(Label::primary(
+        found.to_span(),
+        Some(format!("expected '{}' here", expected)),
+      )),
+    )
+  }
+
+  /// Function that returns `true` when the token under the cursor has the given type.
+  pub fn check(&self, token_type: TokenType) -> bool {
+    self
+      .peek()
+      .is_some_and(|token| token.token_type == token_type)
+  }
+
+  /// Function that returns `true` once the cursor sits on the EOF token.
+  pub fn is_at_end(&self) -> bool {
+    self.peek().is_none_or(Token::is_eof)
+  }
+}
@@ -14,6 +14,8 @@ pub enum TokenType {
   RightBracket,
   Comma,
   Dot,
+  DotDot,
+  DotDotDot,
   Minus,
   MinusEqual,
   MinusMinus,
@@ -24,10 +26,24 @@ pub enum TokenType {
   DivideEqual,
   Multiply,
   MultiplyEqual,
+  Power,
   SemiColon,
   Colon,
   Question,
+  QuestionDot,
+  QuestionQuestion,
   Modulus,
+  ModulusEqual,
+  // Bitwise operators.
+  Ampersand,
+  AmpersandEqual,
+  Pipe,
+  PipeEqual,
+  Caret,
+  CaretEqual,
+  Tilde,
+  ShiftLeft,
+  ShiftRight,
   // One or two character tokens.
   Bang,
   BangEqual,
@@ -37,10 +53,19 @@ pub enum TokenType {
   GreaterEqual,
   Less,
   LessEqual,
+  Arrow,
+  FatArrow,
   // Literals.
   Identifier,
   String,
   Number,
+  // Template strings, a backtick string with `${expr}` interpolations is split into a head, the
+  // middles between interpolations and a tail, each interpolation sits between `${` and `}`.
+  TemplateHead,
+  TemplateMiddle,
+  TemplateTail,
+  InterpolationStart,
+  InterpolationEnd,
   // Keywords.
   And,
   Class,
@@ -62,11 +87,16 @@ pub enum TokenType {
   Break,
   Continue,
   Comment,
+  /// `///` or `//!` comment, the lexeme is the text after the marker
+  DocComment,
+  Newline,
 }
 
-impl fmt::Display for TokenType {
-  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    let s = match self {
+impl TokenType {
+  /// Function that returns the fixed spelling of the token type, or its name for the token types
+  /// that carry a lexeme of their own (identifiers, strings, numbers).
+  pub fn as_str(&self) -> &'static str {
+    match self {
       // Single-character tokens
       TokenType::LeftParen => "(",
       TokenType::RightParen => ")",
@@ -76,6 +106,8 @@ impl fmt::Display for TokenType {
       TokenType::RightBracket => "]",
       TokenType::Comma => ",",
       TokenType::Dot => ".",
+      TokenType::DotDot => "..",
+      TokenType::DotDotDot => "...",
       TokenType::Minus => "-",
       TokenType::MinusEqual => "-=",
       TokenType::MinusMinus => "--",
@@ -86,10 +118,25 @@ impl fmt::Display for TokenType {
       TokenType::DivideEqual => "/=",
       TokenType::Multiply => "*",
       TokenType::MultiplyEqual => "*=",
+      TokenType::Power => "**",
       TokenType::SemiColon => ";",
       TokenType::Colon => ":",
       TokenType::Question => "?",
+      TokenType::QuestionDot => "?.",
+      TokenType::QuestionQuestion => "??",
       TokenType::Modulus => "%",
+      TokenType::ModulusEqual => "%=",
+
+      // Bitwise operators
+      TokenType::Ampersand => "&",
+      TokenType::AmpersandEqual => "&=",
+      TokenType::Pipe => "|",
+      TokenType::PipeEqual => "|=",
+      TokenType::Caret => "^",
+      TokenType::CaretEqual => "^=",
+      TokenType::Tilde => "~",
+      TokenType::ShiftLeft => "<<",
+      TokenType::ShiftRight => ">>",
 
       // One or two character tokens
       TokenType::Bang => "!",
@@ -100,12 +147,21 @@ impl fmt::Display for TokenType {
       TokenType::GreaterEqual => ">=",
       TokenType::Less => "<",
       TokenType::LessEqual => "<=",
+      TokenType::Arrow => "->",
+      TokenType::FatArrow => "=>",
 
       // Literals
       TokenType::Identifier => "identifier",
       TokenType::String => "string",
       TokenType::Number => "number",
 
+      // Template strings
+      TokenType::TemplateHead => "template head",
+      TokenType::TemplateMiddle => "template middle",
+      TokenType::TemplateTail => "template tail",
+      TokenType::InterpolationStart => "${",
+      TokenType::InterpolationEnd => "}",
+
       // Keywords
       TokenType::And => "and",
       TokenType::Class => "class",
@@ -127,8 +183,15 @@ impl fmt::Display for TokenType {
       TokenType::Break => "break",
       TokenType::Continue => "continue",
       TokenType::Comment => "comment",
-    };
-    write!(f, "{}", s)
+      TokenType::DocComment => "doc comment",
+      TokenType::Newline => "newline",
+    }
+  }
+}
+
+impl fmt::Display for TokenType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.as_str())
   }
 }
 
@@ -138,6 +201,7 @@ pub enum Literal {
   String,
   Boolean,
   Nil,
+  Template,
 }
 
 impl std::fmt::Display for Literal {
@@ -147,6 +211,7 @@ impl std::fmt::Display for Literal {
       Literal::String => "String",
       Literal::Boolean => "Boolean",
       Literal::Nil => "Nil",
+      Literal::Template => "Template",
     };
     write!(f, "{}", text)
   }
@@ -1,27 +1,113 @@
+use std::borrow::Cow;
+
 use diagnostic::{
   diagnostic::{Diagnostic, Label, Span},
-  diagnostic_code::DiagnosticCode,
-  DiagnosticEngine,
+  diagnostic_code::{DiagnosticCode, Severity},
 };
 
 use crate::{
+  keywords::KEYWORDS,
   token::{
     types::{Literal, TokenType},
     Token,
   },
-  Scanner,
+  OpenInterpolation, Scanner, ScannerErrorSink,
 };
 
-impl Scanner {
+impl Scanner<'_> {
   /// Function that maps over the "lox" and returns a `Vec<Token>`.
-  pub fn get_tokens(&mut self, engine: &mut DiagnosticEngine) -> () {
+  pub fn get_tokens(&mut self, errors: &mut dyn ScannerErrorSink) {
+    while let Some(token) = self.next_token(errors) {
+      self.tokens.push(token);
+    }
+  }
+
+  /// Function that scans and returns one token at a time so a caller can drive the scanner
+  /// lazily, the EOF token comes once at the end and `None` after it. Tokens are not pushed to
+  /// `tokens`.
+  pub fn next_token(&mut self, errors: &mut dyn ScannerErrorSink) -> Option<Token> {
+    if self.reached_eof {
+      return None;
+    }
+
+    if let Some(token) = self.scan_token(errors) {
+      return Some(token);
+    }
+
+    // The template string around an interpolation still open never closed either, the outermost
+    // one is reported.
+    if let Some(open) = self.template_braces.first() {
+      errors.report_error(
+        self
+          .error(
+            DiagnosticCode::UnterminatedString,
+            "unterminated template interpolation",
+            open.start,
+            2,
+            Some("interpolation starts here"),
+          )
+          .with_help("close the interpolation with '}'".to_string()),
+      );
+      self.template_braces.clear();
+    }
+
+    self.reached_eof = true;
+    Some(Token {
+      token_type: TokenType::Eof,
+      lexeme: Cow::Borrowed(""),
+      literal: Literal::Nil,
+      position: (self.line, self.column),
+      span_start: self.current,
+      span_end: self.current,
+    })
+  }
+
+  /// Function that scans the next token, skipping whitespace and comments, and returns `None` once
+  /// the source is exhausted. The token is returned and not pushed to `tokens`.
+  pub fn scan_token(&mut self, errors: &mut dyn ScannerErrorSink) -> Option<Token> {
+    if self.template_resumes {
+      self.template_resumes = false;
+      self.start = self.current;
+      self.start_column = self.column;
+
+      let token_type = self.tokenize_string_segment(errors, '`', true);
+      return self.make_token(token_type);
+    }
+
     while !self.is_at_end() {
       self.start = self.current;
+      self.start_column = self.column;
       let c = self.advance();
 
       let token = match c {
-        '{' => Some(TokenType::LeftBrace),
-        '}' => Some(TokenType::RightBrace),
+        '{' => {
+          if let Some(open) = self.template_braces.last_mut() {
+            open.braces += 1;
+          }
+          Some(TokenType::LeftBrace)
+        },
+        // A `}` that matches no brace opened inside the interpolation closes it.
+        '}' => match self.template_braces.last_mut() {
+          Some(OpenInterpolation { braces: 0, .. }) => {
+            self.template_braces.pop();
+            self.template_resumes = true;
+            Some(TokenType::InterpolationEnd)
+          },
+          Some(open) => {
+            open.braces -= 1;
+            Some(TokenType::RightBrace)
+          },
+          None => Some(TokenType::RightBrace),
+        },
+        '$' if self.interpolation_pending => {
+          self.interpolation_pending = false;
+          self.advance(); // consume '{'
+          self.template_braces.push(OpenInterpolation {
+            braces: 0,
+            start: (self.line, self.start_column, self.start),
+          });
+          Some(TokenType::InterpolationStart)
+        },
         '(' => Some(TokenType::LeftParen),
         ')' => Some(TokenType::RightParen),
         '[' => Some(TokenType::LeftBracket),
@@ -43,10 +129,10 @@ impl Scanner {
           }
         },
 
-        // Note: '-' is parsed as a unary operator, not part of a number literal.
-        // This keeps expressions like `-n.abs()` and `-123.abs()` consistent,
-        // since method calls have higher precedence than unary minus.
-        // Treating '-' as part of the literal would make `-123.abs()` behave differently.
+        // Note: '-' is always its own token, never part of a number literal. The parser folds
+        // it into a negative literal only when no call or property access follows the number,
+        // so `-123.abs()` stays `-(123.abs())` like `-n.abs()`, since method calls have higher
+        // precedence than unary minus.
         //
         // According to the maximal munch rule, ---a is tokenized as '--' '-a', not '-''--a'.
         // The scanner always chooses the longest valid token, even if it leads to a syntax error later.
@@ -57,12 +143,18 @@ impl Scanner {
           } else if self.match_char(&'=') {
             self.advance();
             Some(TokenType::MinusEqual)
+          } else if self.match_char(&'>') {
+            self.advance();
+            Some(TokenType::Arrow)
           } else {
             Some(TokenType::Minus)
           }
         },
         '*' => {
-          if self.match_char(&'=') {
+          if self.match_char(&'*') {
+            self.advance();
+            Some(TokenType::Power)
+          } else if self.match_char(&'=') {
             self.advance();
             Some(TokenType::MultiplyEqual)
           } else {
@@ -70,40 +162,79 @@ impl Scanner {
           }
         },
 
-        '%' => Some(TokenType::Modulus),
+        '%' => {
+          if self.match_char(&'=') {
+            self.advance();
+            Some(TokenType::ModulusEqual)
+          } else {
+            Some(TokenType::Modulus)
+          }
+        },
 
         // Comment and the Divide
-        '/' => Some(self.tokenize_comments(engine)),
+        '/' => Some(self.tokenize_comments(errors)),
 
         // Strings
-        '"' | '\'' | '`' => Some(self.tokenize_strings(engine)),
+        '"' | '\'' | '`' => Some(self.tokenize_strings(errors)),
 
-        // And condition check
+        // And condition check or bitwise and
         '&' => {
           if self.match_char(&'&') {
             self.advance();
             Some(TokenType::And)
+          } else if self.match_char(&'=') {
+            self.advance();
+            Some(TokenType::AmpersandEqual)
           } else {
-            None
+            Some(TokenType::Ampersand)
           }
         },
 
-        // Ternary operators
-        '?' => Some(TokenType::Question),
+        // Ternary operators, optional chaining and null coalescing
+        //
+        // `?.` followed by a digit stays a ternary so `a ?.5 : b` keeps the `.5` literal.
+        '?' => {
+          if self.match_char(&'?') {
+            self.advance();
+            Some(TokenType::QuestionQuestion)
+          } else if self.match_char(&'.') && !self.peek_next().is_some_and(|c| c.is_ascii_digit()) {
+            self.advance();
+            Some(TokenType::QuestionDot)
+          } else {
+            Some(TokenType::Question)
+          }
+        },
         ':' => Some(TokenType::Colon),
-        // Or condition check
+        // Or condition check or bitwise or
         '|' => {
           if self.match_char(&'|') {
             self.advance();
             Some(TokenType::Or)
+          } else if self.match_char(&'=') {
+            self.advance();
+            Some(TokenType::PipeEqual)
           } else {
-            None
+            Some(TokenType::Pipe)
+          }
+        },
+
+        // Bitwise xor and not
+        '^' => {
+          if self.match_char(&'=') {
+            self.advance();
+            Some(TokenType::CaretEqual)
+          } else {
+            Some(TokenType::Caret)
           }
         },
+        '~' => Some(TokenType::Tilde),
 
         // Comparison And/Or Equality
         '>' => {
-          if self.match_char(&'=') {
+          if self.match_char(&'>') {
+            self.advance();
+            Some(TokenType::ShiftRight)
+          } else if self.match_char(&'=') {
             self.advance();
             Some(TokenType::GreaterEqual)
           } else {
@@ -111,7 +242,10 @@ impl Scanner {
           }
         },
         '<' => {
-          if self.match_char(&'=') {
+          if self.match_char(&'<') {
+            self.advance();
+            Some(TokenType::ShiftLeft)
+          } else if self.match_char(&'=') {
             self.advance();
             Some(TokenType::LessEqual)
           } else {
@@ -119,11 +253,14 @@ impl Scanner {
           }
         },
 
-        // Equal and Strict Equal
+        // Equal, Strict Equal and the fat arrow
         '=' => {
           if self.match_char(&'=') {
             self.advance();
             Some(TokenType::EqualEqual)
+          } else if self.match_char(&'>') {
+            self.advance();
+            Some(TokenType::FatArrow)
           } else {
             Some(TokenType::Equal)
           }
@@ -142,60 +279,68 @@ impl Scanner {
         // SemiColon line Terminator
         ';' => Some(TokenType::SemiColon),
 
-        '.' => self.tokenize_dot(),
+        '.' => self.tokenize_dot(errors),
         ',' => Some(TokenType::Comma),
 
         // Ignore whitespace
-        ' ' | '\r' | '\t' => None,
+        ' ' | '\r' | '\t' | '\x0C' => None,
         // String
         'a'..='z' | 'A'..='Z' | '_' => Some(self.tokenize_keywords()),
+        c if c.is_alphabetic() => Some(self.tokenize_keywords()),
         // Number
-        '0'..='9' => Some(self.tokenize_numbers()),
+        '0'..='9' => Some(self.tokenize_numbers(errors)),
+
+        // New line, a vertical tab counts as one like it did on the terminals it comes from
+        '\n' | '\x0B' => {
+          // The token is made before the line bump so it sits at the end of its own line.
+          let newline = if self.emit_newlines {
+            self.make_token(TokenType::Newline)
+          } else {
+            None
+          };
+          self.new_line();
 
-        // New line
-        '\n' => {
-          self.column = 0;
-          self.line += 1;
+          if newline.is_some() {
+            return newline;
+          }
           None
         },
 
-        // Default case: unrecognized characters
+        // Default case: unrecognized characters, the rest of the word is skipped with it so a
+        // run like `@@@` or `@foo` gives one error instead of a cascade.
         _ => {
-          let diagnostic = Diagnostic::new(
+          let message = format!("unexpected character: {:?}", self.get_current_lexeme());
+          self.synchronize();
+          errors.report_error(self.error(
             DiagnosticCode::InvalidCharacter,
-            format!("unexpected character: {:?}", self.get_current_lexeme()),
-          )
-          .with_label(Label::primary(
-            Span {
-              file: "input".to_string(),
-              line: self.line,
-              column: self.column,
-              length: 1,
-            },
+            &message,
+            (self.line, self.start_column, self.start),
+            self.get_current_lexeme().chars().count(),
             None,
           ));
-
-          engine.emit(diagnostic);
           None
         },
       };
 
-      if let Some(token_type) = token {
-        self.add_token(token_type);
+      if let Some(token) = token.and_then(|token_type| self.make_token(token_type)) {
+        return Some(token);
       };
     }
 
-    self.tokens.push(Token {
-      token_type: TokenType::Eof,
-      lexeme: String::from(""),
-      literal: Literal::Nil,
-      position: (self.line, self.column),
-    });
-
-    ()
+    None
   }
 
-  fn tokenize_dot(&mut self) -> Option<TokenType> {
+  fn tokenize_dot(&mut self, errors: &mut dyn ScannerErrorSink) -> Option<TokenType> {
+    // Dots are checked before digits so `..5` is a range up to `5`, not `.` and `.5`.
+    if self.match_char(&'.') {
+      self.advance();
+      if self.match_char(&'.') {
+        self.advance();
+        return Some(TokenType::DotDotDot);
+      }
+      return Some(TokenType::DotDot);
+    }
+
     if let Some(char) = self.peek() {
       if char.is_ascii_digit() {
         while let Some(char) = self.peek() {
@@ -204,6 +349,11 @@ impl Scanner {
           }
           self.advance();
         }
+
+        // `.5e3` takes its exponent like `0.5e3` does.
+        if matches!(self.peek(), Some('e' | 'E')) {
+          self.tokenize_exponent(errors);
+        }
         return Some(TokenType::Number);
       }
     }
@@ -211,90 +361,241 @@ impl Scanner {
   }
 
   /// Function that tokenize all the string shapes
-  fn tokenize_strings(&mut self, engine: &mut DiagnosticEngine) -> TokenType {
-    let current_char = self.get_current_lexeme().chars().collect::<Vec<_>>()[0];
+  fn tokenize_strings(&mut self, errors: &mut dyn ScannerErrorSink) -> TokenType {
+    // Only the char that opened the string can close it, so `"it's"` is a valid string
+    // while `"hello'` never terminates.
+    let delimiter = self.get_current_lexeme().chars().next().unwrap();
+    self.tokenize_string_segment(errors, delimiter, false)
+  }
+
+  /// Function that scans a string up to its closing `delimiter`, a backtick string also stops
+  /// right before each `${` and gives back a template segment instead. `continues_template` is
+  /// set when the segment follows an interpolation.
+  fn tokenize_string_segment(
+    &mut self,
+    errors: &mut dyn ScannerErrorSink,
+    delimiter: char,
+    continues_template: bool,
+  ) -> TokenType {
+    let (line, column) = (self.line, self.start_column);
+    let closed = if continues_template {
+      TokenType::TemplateTail
+    } else {
+      TokenType::String
+    };
+    self.string_value.clear();
 
     while let Some(char) = self.peek() {
+      if delimiter == '`' && char == '$' && self.peek_next() == Some('{') {
+        self.interpolation_pending = true;
+        return if continues_template {
+          TokenType::TemplateMiddle
+        } else {
+          TokenType::TemplateHead
+        };
+      }
+
       self.advance();
-      if (current_char == '\'' && char == '\'')
-        || (current_char == '"' && char == '"')
-        || (current_char == '`' && char == '`')
-      {
-        break;
+      if char == delimiter {
+        return closed;
+      }
+
+      // A backslash swallows the next char, so `\"` does not close the string.
+      if char == '\\' {
+        if let Some(escape) = self.peek() {
+          let (column, offset) = (self.column - 1, self.current - 1);
+          self.advance();
+
+          let unescaped = match escape {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' | '"' | '\'' | '`' => escape,
+            // Only a template string can start an interpolation, so only it needs `\${`.
+            '$' if delimiter == '`' => escape,
+            'u' => match self.tokenize_unicode_escape(errors, column) {
+              Some(char) => char,
+              None => continue,
+            },
+            _ => {
+              errors.report_error(self.error(
+                DiagnosticCode::InvalidEscapeSequence(escape),
+                &format!("Invalid escape sequence: '\\{}'.", escape),
+                (self.line, column, offset),
+                2,
+                Some("unknown escape"),
+              ));
+              continue;
+            },
+          };
+          self.string_value.push(unescaped);
+        }
+        continue;
       }
 
-      if char == '\n' && current_char != '`' {
-        let diagnostic = Diagnostic::new(
+      if char == '\n' {
+        if delimiter != '`' {
+          errors.report_error(self.error(
+            DiagnosticCode::UnterminatedString,
+            "wrong string syntax",
+            (self.line, self.start_column, self.start),
+            self.get_current_lexeme().chars().count(),
+            Some("newline not allowed in string"),
+          ));
+        }
+
+        self.new_line();
+      }
+
+      self.string_value.push(char);
+    }
+
+    errors.report_error(
+      self
+        .error(
           DiagnosticCode::UnterminatedString,
-          "wrong string syntax".to_string(),
+          "unterminated string",
+          (line, column, self.start),
+          1,
+          Some("string starts here"),
         )
-        .with_label(Label::primary(
-          Span {
-            file: "input".to_string(),
-            line: self.line,
-            column: self.start,
-            length: self.get_current_lexeme().len(),
-          },
-          Some("newline not allowed in string".to_string()),
-        ));
+        .with_help(format!("close the string with a matching {}", delimiter)),
+    );
+    closed
+  }
 
-        engine.emit(diagnostic);
+  /// Function that reads the hex digits of a `\uXXXX` or `\u{X..XXXXXX}` escape, the `\u` is
+  /// already consumed and `column` points at its backslash.
+  fn tokenize_unicode_escape(
+    &mut self,
+    errors: &mut dyn ScannerErrorSink,
+    column: usize,
+  ) -> Option<char> {
+    // `\` and `u` are one byte each.
+    let start = self.current - 2;
+    let braced = self.match_char(&'{');
+    if braced {
+      self.advance();
+    }
+
+    let max_digits = if braced { 6 } else { 4 };
+    let mut digits = String::new();
+    while digits.len() < max_digits {
+      match self.peek() {
+        Some(char) if char.is_ascii_hexdigit() => {
+          digits.push(char);
+          self.advance();
+        },
+        _ => break,
       }
     }
 
-    TokenType::String
+    let well_formed = if braced {
+      !digits.is_empty() && self.match_char(&'}')
+    } else {
+      digits.len() == 4
+    };
+    if braced && well_formed {
+      self.advance(); // consume the "}"
+    }
+
+    let escape = self.source[start..self.current].to_string();
+    let length = escape.chars().count();
+    if !well_formed {
+      errors.report_error(self.error(
+        DiagnosticCode::InvalidEscapeSequence('u'),
+        &format!("Invalid unicode escape sequence: '{}'.", escape),
+        (self.line, column, start),
+        length,
+        Some("expected four hex digits, or one to six inside braces"),
+      ));
+      return None;
+    }
+
+    // Both forms are at most six hex digits, so the value always fits in a `u32`.
+    let code_point = u32::from_str_radix(&digits, 16).unwrap();
+    let char = char::from_u32(code_point);
+    if char.is_none() {
+      errors.report_error(self.error(
+        DiagnosticCode::InvalidLiteral,
+        &format!("Invalid unicode code point: U+{:04X}.", code_point),
+        (self.line, column, start),
+        length,
+        Some("not a unicode scalar value"),
+      ));
+    }
+
+    char
   }
 
   /// Function that tokenize lox comments and if it's not a comment it might a "division" or `None`
-  fn tokenize_comments(&mut self, engine: &mut DiagnosticEngine) -> TokenType {
+  fn tokenize_comments(&mut self, errors: &mut dyn ScannerErrorSink) -> TokenType {
     if self.match_char(&'=') {
       self.advance();
       TokenType::DivideEqual
     } else if self.match_char(&'/') {
-      loop {
-        match self.advance() {
-          char => {
-            if char == '\n' {
-              break;
-            };
-          },
-        }
-      }
+      self.advance(); // consume the second "/"
 
-      TokenType::Comment
-    } else if self.match_char(&'*') {
-      // Checking for the block comment
-      while !self.is_at_end() {
-        let char = self.peek().unwrap();
-        if char == '*' && self.peek_next().unwrap() == '/' {
-          self.advance();
-          self.advance();
-          break;
-        }
+      // `///` and `//!` are doc comments, a run of four or more slashes is a plain one.
+      let is_doc = match self.peek() {
+        Some('/') => self.peek_next() != Some('/'),
+        Some('!') => true,
+        _ => false,
+      };
 
-        let char = self.advance();
+      // The newline is left for the main loop so it bumps the line count.
+      while let Some(char) = self.peek() {
         if char == '\n' {
-          self.column = 0;
-          self.line += 1;
+          break;
         }
+        self.advance();
       }
 
-      if self.is_at_end() {
-        let diagnostic = Diagnostic::new(
-          DiagnosticCode::UnterminatedString,
-          "unterminated multi-line comment".to_string(),
-        )
-        .with_label(Label::primary(
-          Span {
-            file: "input".to_string(),
-            line: self.line,
-            column: self.column,
-            length: self.get_current_lexeme().len(),
-          },
-          Some("reached end of file before closing comment".to_string()),
-        ));
+      if is_doc {
+        TokenType::DocComment
+      } else {
+        TokenType::Comment
+      }
+    } else if self.match_char(&'*') {
+      // The opening `*` is consumed first so `/*/` doesn't close itself.
+      self.advance();
+      let (line, column) = (self.line, self.start_column);
 
-        engine.emit(diagnostic);
+      loop {
+        match self.peek() {
+          Some('*') if self.peek_next() == Some('/') => {
+            self.advance();
+            self.advance();
+            break;
+          },
+          Some('/') if self.peek_next() == Some('*') => {
+            errors.report_error(self.warning(
+              DiagnosticCode::NestedComment,
+              "'/*' inside a comment, block comments don't nest",
+              (self.line, self.column, self.current),
+              2,
+              Some("the first '*/' closes the outer comment"),
+            ));
+            self.advance();
+            self.advance();
+          },
+          Some(_) => {
+            if self.advance() == '\n' {
+              self.new_line();
+            }
+          },
+          None => {
+            errors.report_error(self.error(
+              DiagnosticCode::UnterminatedComment,
+              "unterminated multi-line comment",
+              (line, column, self.start),
+              2,
+              Some("comment starts here"),
+            ));
+            break;
+          },
+        }
       }
       TokenType::Comment
     } else {
@@ -303,107 +604,242 @@ impl Scanner {
   }
 
   /// Function that tokenize lox numbers and return `TokenType`.
-  fn tokenize_numbers(&mut self) -> TokenType {
+  fn tokenize_numbers(&mut self, errors: &mut dyn ScannerErrorSink) -> TokenType {
+    // Only the first digit is consumed so far, a prefix is that `0` and the char after it.
+    if let Some(radix) = self
+      .source
+      .get(self.start..=self.current)
+      .and_then(Self::radix_of)
+    {
+      return self.tokenize_radix_number(errors, radix);
+    }
+
     while let Some(char) = self.peek() {
-      if char.is_ascii_digit() {
-        self.advance();
-      } else {
-        if self.match_char(&'.') {
-          match self.peek_next() {
-            Some(char) => {
-              if char.is_ascii_digit() {
-                self.advance();
-              } else {
-                break;
-              }
-            },
-            None => {
+      if !char.is_ascii_digit() {
+        break;
+      }
+      self.advance();
+    }
+
+    if self.match_char(&'.') {
+      match self.peek_next() {
+        Some(char) if char.is_ascii_digit() => {
+          self.advance(); // consume the "."
+          while let Some(char) = self.peek() {
+            if !char.is_ascii_digit() {
               break;
-            },
+            }
+            self.advance();
           }
-        } else {
-          break;
-        }
+        },
+        // `1.abs()` and `1..2` keep the dot for the member access / range.
+        Some(char) if char.is_ascii_alphabetic() || char == '_' || char == '.' => {},
+        // A trailing dot (`1.`) still belongs to the literal, it marks it as a float.
+        _ => {
+          self.advance();
+        },
       }
     }
 
+    if matches!(self.peek(), Some('e' | 'E')) {
+      self.tokenize_exponent(errors);
+    }
+
+    TokenType::Number
+  }
+
+  /// Function that consumes the `e` exponent of a number, `e` or `E` then an optional sign and
+  /// the integer power.
+  fn tokenize_exponent(&mut self, errors: &mut dyn ScannerErrorSink) {
+    self.advance(); // consume the "e"
+    if matches!(self.peek(), Some('+' | '-')) {
+      self.advance();
+    }
+
+    let digits = self.current;
+    self.consume_digits();
+
+    if self.current == digits {
+      errors.report_error(
+        self
+          .error(
+            DiagnosticCode::InvalidNumber,
+            &format!("Invalid number literal '{}'", self.get_current_lexeme()),
+            (self.line, self.start_column, self.start),
+            self.get_current_lexeme().chars().count(),
+            Some("missing exponent digits"),
+          )
+          .with_help("write the power after the exponent, e.g. `1e3`".to_string()),
+      );
+    } else if self.match_char(&'.') && self.peek_next().is_some_and(|char| char.is_ascii_digit()) {
+      self.advance(); // consume the "."
+      self.consume_digits();
+      errors.report_error(self.error(
+        DiagnosticCode::InvalidNumber,
+        &format!("Invalid number literal '{}'", self.get_current_lexeme()),
+        (self.line, self.start_column, self.start),
+        self.get_current_lexeme().chars().count(),
+        Some("the exponent must be an integer"),
+      ));
+    }
+  }
+
+  /// Function that consumes the digits of a prefixed literal like `0xFF`, `0o17` or `0b1010`, the
+  /// `0` is already consumed. A letter or digit outside the radix stops it and is reported.
+  fn tokenize_radix_number(&mut self, errors: &mut dyn ScannerErrorSink, radix: u32) -> TokenType {
+    self.advance(); // consume the "x", "o" or "b"
+    let kind = match radix {
+      16 => "hexadecimal",
+      8 => "octal",
+      _ => "binary",
+    };
+
+    let digits = self.current;
+    while self.peek().is_some_and(|char| char.is_digit(radix)) {
+      self.advance();
+    }
+
+    if let Some(char) = self.peek().filter(|char| char.is_ascii_alphanumeric()) {
+      errors.report_error(self.error(
+        DiagnosticCode::InvalidNumber,
+        &format!("Invalid digit '{}' in number literal", char),
+        (self.line, self.column, self.current),
+        1,
+        Some(&format!("not a {} digit", kind)),
+      ));
+    } else if self.current == digits {
+      errors.report_error(
+        self
+          .error(
+            DiagnosticCode::InvalidNumber,
+            &format!("Invalid number literal '{}'", self.get_current_lexeme()),
+            (self.line, self.start_column, self.start),
+            self.get_current_lexeme().chars().count(),
+            Some("missing digits"),
+          )
+          .with_help(
+            "write the digits after the prefix, e.g. `0xFF`, `0o17` or `0b1010`".to_string(),
+          ),
+      );
+    }
+
     TokenType::Number
   }
 
+  /// Function that returns the radix named by the `0x`, `0o` or `0b` prefix of `lexeme`.
+  fn radix_of(lexeme: &str) -> Option<u32> {
+    match lexeme.get(..2)? {
+      "0x" | "0X" => Some(16),
+      "0o" | "0O" => Some(8),
+      "0b" | "0B" => Some(2),
+      _ => None,
+    }
+  }
+
+  fn consume_digits(&mut self) {
+    while let Some(char) = self.peek() {
+      if !char.is_ascii_digit() {
+        break;
+      }
+      self.advance();
+    }
+  }
+
   /// Function that tokenize lox keywords and return `TokenType`.
   fn tokenize_keywords(&mut self) -> TokenType {
     while let Some(char) = self.peek() {
-      if char.is_ascii_alphanumeric() || char == '_' {
+      if char.is_alphanumeric() || char == '_' {
         self.advance();
       } else {
         break;
       }
     }
 
-    match self.get_current_lexeme() {
-      "var" => TokenType::Var,
-      "fun" => TokenType::Fun,
-      "return" => TokenType::Return,
-      "if" => TokenType::If,
-      "else" => TokenType::Else,
-      "for" => TokenType::For,
-      "while" => TokenType::While,
-      "break" => TokenType::Break,
-      "continue" => TokenType::Continue,
-      "class" => TokenType::Class,
-      "this" => TokenType::This,
-      "true" => TokenType::True,
-      "false" => TokenType::False,
-      "nil" => TokenType::Nil,
-      "or" => TokenType::Or,
-      "and" => TokenType::And,
-      "super" => TokenType::Super,
-      _ => TokenType::Identifier,
-    }
+    let lexeme = self.get_current_lexeme();
+    self
+      .keywords
+      .get(lexeme)
+      .or_else(|| KEYWORDS.get(lexeme))
+      .cloned()
+      .unwrap_or(TokenType::Identifier)
   }
 
-  /// Function that takes "token_type" and push a struct token to the `Vec<Token>`.
-  fn add_token(&mut self, token_type: TokenType) {
-    let mut lexeme = self.get_current_lexeme().to_string();
+  /// Function that takes "token_type" and builds the token for the current lexeme, comments don't
+  /// produce a token.
+  fn make_token(&self, token_type: TokenType) -> Option<Token> {
+    let current = self.get_current_lexeme();
     let literal = self.get_literal(&token_type);
 
-    match token_type {
-      TokenType::Comment => {
-        // println!("Comment: {}", lexeme);
-        return; // don't add comment tokens
+    // The segment after an interpolation is empty in `${a}${b}` or when the source ends there.
+    debug_assert!(
+      !current.is_empty()
+        || matches!(
+          token_type,
+          TokenType::Eof | TokenType::TemplateMiddle | TokenType::TemplateTail
+        ),
+      "Empty lexeme for {:?}",
+      token_type
+    );
+
+    let lexeme: Cow<'static, str> = match token_type {
+      TokenType::Comment | TokenType::DocComment if !self.emit_comments => {
+        return None; // don't add comment tokens
       },
 
-      TokenType::String => {
-        // Remove the quotes from the string literal
-        if lexeme.len() >= 2 {
-          lexeme = lexeme[1..lexeme.len() - 1].to_string();
-        }
+      // Strip the `///` or `//!` marker and the space after it.
+      TokenType::DocComment => current[3..].trim_start().to_string().into(),
+
+      // The quotes are dropped and escape sequences are already resolved while scanning.
+      TokenType::String if current.len() >= 2 => self.string_value.clone().into(),
+      TokenType::TemplateHead | TokenType::TemplateMiddle | TokenType::TemplateTail => {
+        self.string_value.clone().into()
+      },
+
+      // Prefixed literals are stored as their decimal value, so the rest of the pipeline only
+      // ever parses decimal lexemes.
+      TokenType::Number if Self::radix_of(current).is_some() => {
+        let radix = Self::radix_of(current).unwrap();
+        let value = current[2..].chars().fold(0.0, |value, digit| {
+          value * f64::from(radix) + f64::from(digit.to_digit(radix).unwrap())
+        });
+        value.to_string().into()
       },
 
       TokenType::Number => {
-        // Normalize numbers like `.5` → `0.5` and `5.` → `5`
-        if lexeme.ends_with('.') {
-          lexeme = lexeme.trim_end_matches('.').to_string();
-        } else if lexeme.starts_with('.') {
-          lexeme = format!("0{}", lexeme);
+        // Normalize floats like `.5` → `0.5` and `5.` → `5.0`, so a float lexeme always
+        // keeps a digit on both sides of the decimal point.
+        if current.ends_with('.') {
+          format!("{}0", current).into()
+        } else if current.starts_with('.') {
+          format!("0{}", current).into()
+        } else {
+          current.to_string().into()
         }
       },
 
-      _ => {},
-    }
+      // Operators and keywords always read the same, so they borrow the static spelling.
+      _ if token_type.as_str() == current => Cow::Borrowed(token_type.as_str()),
+
+      _ => current.to_string().into(),
+    };
 
-    self.tokens.push(Token {
+    Some(Token {
       token_type,
       lexeme,
       literal,
-      position: (self.line, self.column),
-    });
+      position: (self.line, self.start_column),
+      span_start: self.start,
+      span_end: self.current,
+    })
   }
 
   /// Function that gets the literal type of the token.
   fn get_literal(&self, token_type: &TokenType) -> Literal {
     match token_type {
       TokenType::String => Literal::String,
+      TokenType::TemplateHead | TokenType::TemplateMiddle | TokenType::TemplateTail => {
+        Literal::Template
+      },
       TokenType::Number => Literal::Number,
       TokenType::True => Literal::Boolean,
       TokenType::False => Literal::Boolean,
@@ -411,19 +847,100 @@ impl Scanner {
     }
   }
 
+  /// Function that builds an error diagnostic labelled at `position`, the caller emits it once
+  /// any help or note is attached. Only error codes are accepted here.
+  fn error(
+    &self,
+    code: DiagnosticCode,
+    message: &str,
+    position: (usize, usize, usize),
+    length: usize,
+    label: Option<&str>,
+  ) -> Diagnostic {
+    debug_assert_eq!(
+      code.severity(),
+      Severity::Error,
+      "{:?} is not an error code",
+      code
+    );
+    Self::labelled(code, message, position, length, label)
+  }
+
+  /// Function that builds a warning diagnostic labelled at `position`, like [`Self::error`] but
+  /// only for warning codes so scanning still proceeds.
+  fn warning(
+    &self,
+    code: DiagnosticCode,
+    message: &str,
+    position: (usize, usize, usize),
+    length: usize,
+    label: Option<&str>,
+  ) -> Diagnostic {
+    debug_assert_eq!(
+      code.severity(),
+      Severity::Warning,
+      "{:?} is not a warning code",
+      code
+    );
+    Self::labelled(code, message, position, length, label)
+  }
+
+  /// Function that builds a diagnostic with a single primary label at `(line, column, offset)`.
+  fn labelled(
+    code: DiagnosticCode,
+    message: &str,
+    (line, column, byte_offset): (usize, usize, usize),
+    length: usize,
+    label: Option<&str>,
+  ) -> Diagnostic {
+    Diagnostic::new(code, message.to_string()).with_label(Label::primary(
+      Span {
+        file: "input".to_string(),
+        line,
+        column,
+        length,
+        byte_offset,
+      },
+      label.map(|label| label.to_string()),
+    ))
+  }
+
+  /// Function that skips to the next whitespace after a scan error. Brackets, separators and
+  /// quotes stop it early so the parser still sees the structure around the bad chars.
+  fn synchronize(&mut self) {
+    while let Some(char) = self.peek() {
+      let is_boundary = matches!(
+        char,
+        ';' | ',' | '(' | ')' | '{' | '}' | '[' | ']' | '"' | '\'' | '`'
+      );
+      if char.is_whitespace() || is_boundary {
+        break;
+      }
+      self.advance();
+    }
+  }
+
+  /// Function that moves the position to the start of the next line, called right after a `\n`
+  /// is consumed so the first char of the line is at column 1.
+  fn new_line(&mut self) {
+    self.line += 1;
+    self.column = 1;
+  }
+
   /// Function that returns `bool` which indicate the state at the "EOF".
   fn is_at_end(&self) -> bool {
-    (self.current as usize) == self.source.len()
+    self.current == self.source.len()
   }
 
-  /// Function that return the next char and shift the current and column count to this char.
+  /// Function that return the next char and shift the current and column count to this char,
+  /// `current` moves by the char's UTF-8 width while `column` counts chars.
   fn advance(&mut self) -> char {
-    let char = self.peek();
+    let char = self.peek().unwrap();
 
-    self.current += 1;
+    self.current += char.len_utf8();
     self.column += 1;
 
-    char.unwrap()
+    char
   }
 
   /// Function that returns the next char without advancing the pointer.
@@ -432,43 +949,34 @@ impl Scanner {
       return None;
     };
 
-    let char = self.source[(self.current as usize)..]
-      .chars()
-      .next()
-      .unwrap();
+    let char = self.source[self.current..].chars().next().unwrap();
 
     Some(char)
   }
+
+  /// Function that returns the char after the next one without advancing the pointer.
   fn peek_next(&self) -> Option<char> {
     if self.is_at_end() {
       return None;
     };
 
-    let char = self.source[((self.current + 1) as usize)..]
-      .chars()
-      .next()
-      .unwrap();
-
-    Some(char)
+    self.source[self.current..].chars().nth(1)
   }
 
   /// Function that returns the current lexelme.
   fn get_current_lexeme(&self) -> &str {
-    return &self.source[(self.start as usize)..(self.current as usize)];
+    &self.source[self.start..self.current]
   }
 
-  /// Function that matches the next char to an argument and returns true.
+  /// Function that returns true when the next char is `expected`. It never consumes the char,
+  /// a caller that takes it as part of the token follows up with `advance`, which lets the same
+  /// check also serve as a plain lookahead (`1.abs()`, `a ?.5 : b`).
   fn match_char(&self, expected: &char) -> bool {
     if self.is_at_end() {
       return false;
     }
 
-    if &self.source[(self.current as usize)..]
-      .chars()
-      .next()
-      .unwrap()
-      != expected
-    {
+    if &self.source[self.current..].chars().next().unwrap() != expected {
       return false;
     }
 
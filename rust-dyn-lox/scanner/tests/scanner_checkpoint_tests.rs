@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::DiagnosticEngine;
+  use scanner::{
+    token::{types::TokenType, Token},
+    Scanner,
+  };
+
+  fn next(scanner: &mut Scanner, engine: &mut DiagnosticEngine) -> Token {
+    scanner.scan_token(engine).unwrap()
+  }
+
+  fn rest(scanner: &mut Scanner, engine: &mut DiagnosticEngine) -> Vec<Token> {
+    std::iter::from_fn(|| scanner.next_token(engine)).collect()
+  }
+
+  #[test]
+  fn test_restore_position_rescans_the_same_tokens() {
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new("var a = foo(1, \"two\");".to_string());
+
+    for _ in 0..3 {
+      next(&mut scanner, &mut engine);
+    }
+
+    let checkpoint = scanner.save_position();
+    let first = vec![
+      next(&mut scanner, &mut engine),
+      next(&mut scanner, &mut engine),
+    ];
+
+    scanner.restore_position(checkpoint);
+    let second = vec![
+      next(&mut scanner, &mut engine),
+      next(&mut scanner, &mut engine),
+    ];
+
+    assert_eq!(first, second);
+    assert_eq!(first[0].lexeme, "foo");
+    assert_eq!(first[1].lexeme, "(");
+    assert!(!engine.has_errors());
+  }
+
+  #[test]
+  fn test_restore_position_inside_a_template_string() {
+    let source = "`a ${ {b: 1} } c ${d}` + e";
+    let mut engine = DiagnosticEngine::new();
+
+    // A checkpoint before and after every token of the template rescans the same rest.
+    for skipped in 0..12 {
+      let mut scanner = Scanner::new(source);
+      for _ in 0..skipped {
+        scanner.next_token(&mut engine);
+      }
+
+      let checkpoint = scanner.save_position();
+      let first = rest(&mut scanner, &mut engine);
+      scanner.restore_position(checkpoint);
+      let second = rest(&mut scanner, &mut engine);
+
+      assert_eq!(first, second, "after {} tokens", skipped);
+      assert_eq!(first.last().unwrap().token_type, TokenType::Eof);
+    }
+    assert!(!engine.has_errors());
+  }
+
+  #[test]
+  fn test_scan_token_ends_with_none() {
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new("// only a comment\n".to_string());
+
+    assert!(scanner.scan_token(&mut engine).is_none());
+    assert!(scanner.tokens.is_empty());
+  }
+
+  #[test]
+  fn test_next_token_ends_with_a_single_eof() {
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new("a + 1".to_string());
+
+    let checkpoint = scanner.save_position();
+    let mut token_types = vec![];
+    while let Some(token) = scanner.next_token(&mut engine) {
+      token_types.push(token.token_type);
+    }
+    assert_eq!(
+      token_types,
+      vec![
+        TokenType::Identifier,
+        TokenType::Plus,
+        TokenType::Number,
+        TokenType::Eof,
+      ]
+    );
+    assert!(scanner.next_token(&mut engine).is_none());
+    assert!(scanner.tokens.is_empty());
+
+    // Rewinding to a checkpoint taken before the EOF scans from there again.
+    scanner.restore_position(checkpoint);
+    assert_eq!(
+      scanner.next_token(&mut engine).unwrap().token_type,
+      TokenType::Identifier
+    );
+  }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::DiagnosticEngine;
+  use scanner::{token::types::TokenType, Scanner};
+
+  fn token_types(source: &str) -> Vec<TokenType> {
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new(source.to_string());
+    scanner.scan(&mut engine);
+
+    assert!(!engine.has_errors());
+    scanner
+      .tokens
+      .iter()
+      .map(|token| token.token_type.clone())
+      .collect()
+  }
+
+  #[test]
+  fn test_postfix_increment() {
+    assert_eq!(
+      token_types("i++;"),
+      vec![
+        TokenType::Identifier,
+        TokenType::PlusPlus,
+        TokenType::SemiColon,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_prefix_increment() {
+    assert_eq!(
+      token_types("++i;"),
+      vec![
+        TokenType::PlusPlus,
+        TokenType::Identifier,
+        TokenType::SemiColon,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_postfix_decrement() {
+    assert_eq!(
+      token_types("i--;"),
+      vec![
+        TokenType::Identifier,
+        TokenType::MinusMinus,
+        TokenType::SemiColon,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_compound_assignment_is_not_an_increment() {
+    assert_eq!(
+      token_types("i += 1;"),
+      vec![
+        TokenType::Identifier,
+        TokenType::PlusEqual,
+        TokenType::Number,
+        TokenType::SemiColon,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_three_pluses_take_the_longest_token_first() {
+    assert_eq!(
+      token_types("i+++j"),
+      vec![
+        TokenType::Identifier,
+        TokenType::PlusPlus,
+        TokenType::Plus,
+        TokenType::Identifier,
+        TokenType::Eof,
+      ]
+    );
+  }
+}
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::DiagnosticEngine;
+  use scanner::{token::types::TokenType, Scanner};
+
+  /// One source fragment per token type, in the order they are declared in `TokenType`.
+  ///
+  /// `NullChar` is never produced by the scanner, comment tokens are dropped and `Newline` is
+  /// opt-in, so none has an entry; `Eof` always closes the stream. Template tokens only come in
+  /// groups, they are covered by the template string tests instead.
+  const FRAGMENTS: &[(&str, TokenType)] = &[
+    ("(", TokenType::LeftParen),
+    (")", TokenType::RightParen),
+    ("{", TokenType::LeftBrace),
+    ("}", TokenType::RightBrace),
+    ("[", TokenType::LeftBracket),
+    ("]", TokenType::RightBracket),
+    (",", TokenType::Comma),
+    (".", TokenType::Dot),
+    ("..", TokenType::DotDot),
+    ("...", TokenType::DotDotDot),
+    ("-", TokenType::Minus),
+    ("-=", TokenType::MinusEqual),
+    ("--", TokenType::MinusMinus),
+    ("+", TokenType::Plus),
+    ("+=", TokenType::PlusEqual),
+    ("++", TokenType::PlusPlus),
+    ("/", TokenType::Divide),
+    ("/=", TokenType::DivideEqual),
+    ("*", TokenType::Multiply),
+    ("*=", TokenType::MultiplyEqual),
+    ("**", TokenType::Power),
+    (";", TokenType::SemiColon),
+    (":", TokenType::Colon),
+    ("?", TokenType::Question),
+    ("?.", TokenType::QuestionDot),
+    ("??", TokenType::QuestionQuestion),
+    ("%", TokenType::Modulus),
+    ("%=", TokenType::ModulusEqual),
+    ("&", TokenType::Ampersand),
+    ("&=", TokenType::AmpersandEqual),
+    ("|", TokenType::Pipe),
+    ("|=", TokenType::PipeEqual),
+    ("^", TokenType::Caret),
+    ("^=", TokenType::CaretEqual),
+    ("~", TokenType::Tilde),
+    ("<<", TokenType::ShiftLeft),
+    (">>", TokenType::ShiftRight),
+    ("!", TokenType::Bang),
+    ("!=", TokenType::BangEqual),
+    ("=", TokenType::Equal),
+    ("==", TokenType::EqualEqual),
+    (">", TokenType::Greater),
+    (">=", TokenType::GreaterEqual),
+    ("<", TokenType::Less),
+    ("<=", TokenType::LessEqual),
+    ("->", TokenType::Arrow),
+    ("=>", TokenType::FatArrow),
+    ("name", TokenType::Identifier),
+    ("\"text\"", TokenType::String),
+    ("42", TokenType::Number),
+    ("and", TokenType::And),
+    ("class", TokenType::Class),
+    ("else", TokenType::Else),
+    ("false", TokenType::False),
+    ("fun", TokenType::Fun),
+    ("for", TokenType::For),
+    ("if", TokenType::If),
+    ("nil", TokenType::Nil),
+    ("or", TokenType::Or),
+    ("return", TokenType::Return),
+    ("super", TokenType::Super),
+    ("this", TokenType::This),
+    ("true", TokenType::True),
+    ("var", TokenType::Var),
+    ("while", TokenType::While),
+    ("break", TokenType::Break),
+    ("continue", TokenType::Continue),
+  ];
+
+  /// Fails to compile when a `TokenType` is added, as a reminder to extend `FRAGMENTS`.
+  fn is_scannable(token_type: &TokenType) -> bool {
+    match token_type {
+      TokenType::NullChar
+      | TokenType::Comment
+      | TokenType::DocComment
+      | TokenType::Eof
+      | TokenType::Newline => false,
+      TokenType::TemplateHead
+      | TokenType::TemplateMiddle
+      | TokenType::TemplateTail
+      | TokenType::InterpolationStart
+      | TokenType::InterpolationEnd => false,
+      TokenType::LeftParen
+      | TokenType::RightParen
+      | TokenType::LeftBrace
+      | TokenType::RightBrace
+      | TokenType::LeftBracket
+      | TokenType::RightBracket
+      | TokenType::Comma
+      | TokenType::Dot
+      | TokenType::DotDot
+      | TokenType::DotDotDot
+      | TokenType::Minus
+      | TokenType::MinusEqual
+      | TokenType::MinusMinus
+      | TokenType::Plus
+      | TokenType::PlusEqual
+      | TokenType::PlusPlus
+      | TokenType::Divide
+      | TokenType::DivideEqual
+      | TokenType::Multiply
+      | TokenType::MultiplyEqual
+      | TokenType::Power
+      | TokenType::SemiColon
+      | TokenType::Colon
+      | TokenType::Question
+      | TokenType::QuestionDot
+      | TokenType::QuestionQuestion
+      | TokenType::Modulus
+      | TokenType::ModulusEqual
+      | TokenType::Ampersand
+      | TokenType::AmpersandEqual
+      | TokenType::Pipe
+      | TokenType::PipeEqual
+      | TokenType::Caret
+      | TokenType::CaretEqual
+      | TokenType::Tilde
+      | TokenType::ShiftLeft
+      | TokenType::ShiftRight
+      | TokenType::Bang
+      | TokenType::BangEqual
+      | TokenType::Equal
+      | TokenType::EqualEqual
+      | TokenType::Greater
+      | TokenType::GreaterEqual
+      | TokenType::Less
+      | TokenType::LessEqual
+      | TokenType::Arrow
+      | TokenType::FatArrow
+      | TokenType::Identifier
+      | TokenType::String
+      | TokenType::Number
+      | TokenType::And
+      | TokenType::Class
+      | TokenType::Else
+      | TokenType::False
+      | TokenType::Fun
+      | TokenType::For
+      | TokenType::If
+      | TokenType::Nil
+      | TokenType::Or
+      | TokenType::Return
+      | TokenType::Super
+      | TokenType::This
+      | TokenType::True
+      | TokenType::Var
+      | TokenType::While
+      | TokenType::Break
+      | TokenType::Continue => true,
+    }
+  }
+
+  #[test]
+  fn test_every_token_type_round_trips() {
+    // Fragments are space separated so two-character operators don't merge with their neighbours.
+    let source = FRAGMENTS
+      .iter()
+      .map(|(fragment, _)| *fragment)
+      .collect::<Vec<_>>()
+      .join(" ");
+
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new(source);
+    scanner.scan(&mut engine);
+
+    assert!(!engine.has_errors());
+
+    let mut expected = FRAGMENTS
+      .iter()
+      .map(|(_, token_type)| token_type.clone())
+      .collect::<Vec<_>>();
+    assert!(expected.iter().all(is_scannable));
+    expected.push(TokenType::Eof);
+
+    let scanned = scanner
+      .tokens
+      .iter()
+      .map(|token| token.token_type.clone())
+      .collect::<Vec<_>>();
+    assert_eq!(scanned, expected);
+  }
+}
@@ -1,20 +1,1125 @@
 #[cfg(test)]
 mod tests {
-  use scanner::{lox::Lox, token::types::TokenType, Scanner};
+  use std::{borrow::Cow, collections::HashMap};
 
-  use super::*;
+  use diagnostic::{diagnostic::Diagnostic, diagnostic_code::DiagnosticCode, DiagnosticEngine};
+  use scanner::{
+    token::{
+      types::{Literal, TokenType},
+      Token,
+    },
+    Scanner, ScannerErrorSink,
+  };
+
+  fn scan(source: &str) -> (Vec<Token>, DiagnosticEngine) {
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new(source.to_string());
+    scanner.scan(&mut engine);
+
+    (scanner.tokens, engine)
+  }
+
+  fn token_types(tokens: &[Token]) -> Vec<TokenType> {
+    tokens.iter().map(|t| t.token_type.clone()).collect()
+  }
 
   #[test]
   fn test_identifier_and_equal() {
-    let mut lox = Lox::new();
-    let mut scanner = Scanner::new();
-    scanner.run_file(
-      String::from("tests/files/test_identifier_and_equal.duck"),
-      &mut lox,
-    );
-    let tokens = scanner.tokens;
+    let source = std::fs::read_to_string("tests/files/test_identifier_and_equal.duck").unwrap();
+    let (tokens, engine) = scan(&source);
 
+    assert!(!engine.has_errors());
     assert_eq!(tokens[0].token_type, TokenType::Var);
     assert_eq!(tokens.last().unwrap().token_type, TokenType::Eof);
   }
+
+  #[test]
+  fn test_float_lexeme_normalization() {
+    for (source, lexeme) in [("0.", "0.0"), ("1.", "1.0"), (".5", "0.5"), ("1.0", "1.0")] {
+      let (tokens, engine) = scan(source);
+
+      assert!(!engine.has_errors(), "unexpected error for {:?}", source);
+      assert_eq!(tokens.len(), 2, "expected a single number for {:?}", source);
+      assert_eq!(tokens[0].token_type, TokenType::Number);
+      assert_eq!(tokens[0].lexeme, lexeme);
+    }
+  }
+
+  #[test]
+  fn test_integer_lexeme_is_untouched() {
+    let (tokens, _) = scan("42;");
+
+    assert_eq!(tokens[0].token_type, TokenType::Number);
+    assert_eq!(tokens[0].lexeme, "42");
+  }
+
+  #[test]
+  fn test_method_call_on_float() {
+    let (tokens, engine) = scan("1.0.to_string()");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::Number,
+        TokenType::Dot,
+        TokenType::Identifier,
+        TokenType::LeftParen,
+        TokenType::RightParen,
+        TokenType::Eof,
+      ]
+    );
+    assert_eq!(tokens[0].lexeme, "1.0");
+  }
+
+  #[test]
+  fn test_method_call_on_integer() {
+    let (tokens, engine) = scan("1.to_string()");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::Number,
+        TokenType::Dot,
+        TokenType::Identifier,
+        TokenType::LeftParen,
+        TokenType::RightParen,
+        TokenType::Eof,
+      ]
+    );
+    assert_eq!(tokens[0].lexeme, "1");
+    assert_eq!(tokens[2].lexeme, "to_string");
+  }
+
+  #[test]
+  fn test_plain_float() {
+    let (tokens, engine) = scan("1.0");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![TokenType::Number, TokenType::Eof]
+    );
+    assert_eq!(tokens[0].lexeme, "1.0");
+  }
+
+  #[test]
+  fn test_string_mismatched_delimiters() {
+    for source in ["\"hello'", "'world\""] {
+      let (_, engine) = scan(source);
+
+      assert!(engine.has_errors(), "expected an error for {}", source);
+      assert_eq!(
+        engine.get_diagnostics()[0].code,
+        DiagnosticCode::UnterminatedString
+      );
+    }
+  }
+
+  #[test]
+  fn test_string_other_quote_inside() {
+    let (tokens, engine) = scan("\"it's fine\"");
+
+    assert!(!engine.has_errors());
+    assert_eq!(tokens[0].token_type, TokenType::String);
+    assert_eq!(tokens[0].lexeme, "it's fine");
+  }
+
+  #[test]
+  fn test_string_escaped_quote_does_not_terminate() {
+    let (tokens, engine) = scan(r#""say \"hi\"" + 1;"#);
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::String,
+        TokenType::Plus,
+        TokenType::Number,
+        TokenType::SemiColon,
+        TokenType::Eof,
+      ]
+    );
+    assert_eq!(tokens[0].lexeme, r#"say "hi""#);
+  }
+
+  #[test]
+  fn test_string_escape_sequences_are_unescaped() {
+    let (tokens, engine) = scan(r#""a\nb\tc\r\\ \' \` \0""#);
+
+    assert!(!engine.has_errors());
+    assert_eq!(tokens[0].lexeme, "a\nb\tc\r\\ ' ` \0");
+  }
+
+  #[test]
+  fn test_string_unicode_escape() {
+    let (tokens, engine) = scan(r#""caf\u00e9 \u2603""#);
+
+    assert!(!engine.has_errors());
+    assert_eq!(tokens[0].lexeme, "café ☃");
+  }
+
+  #[test]
+  fn test_string_short_unicode_escape_is_an_error() {
+    let (tokens, engine) = scan(r#""x\u12g""#);
+
+    assert_eq!(engine.error_count(), 1);
+    let diagnostic = &engine.get_diagnostics()[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::InvalidEscapeSequence('u'));
+    assert_eq!(diagnostic.labels[0].span.column, 3);
+    assert_eq!(tokens[0].lexeme, "xg");
+  }
+
+  #[test]
+  fn test_string_trailing_backslash_is_unterminated() {
+    let (_, engine) = scan(r#""oops\""#);
+
+    assert!(engine.has_errors());
+    assert_eq!(
+      engine.get_diagnostics()[0].code,
+      DiagnosticCode::UnterminatedString
+    );
+  }
+
+  #[test]
+  fn test_string_invalid_escape_sequence() {
+    let (tokens, engine) = scan(r#"var s = "a\qb\n";"#);
+
+    assert_eq!(engine.error_count(), 1);
+    let diagnostic = &engine.get_diagnostics()[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::InvalidEscapeSequence('q'));
+    assert_eq!(diagnostic.message, r"Invalid escape sequence: '\q'.");
+    assert_eq!(diagnostic.labels[0].span.column, 11);
+    assert_eq!(
+      DiagnosticCode::InvalidEscapeSequence('q').to_string(),
+      r"invalid escape sequence: '\q'"
+    );
+
+    // The string still scans, so the rest of the statement is kept.
+    assert_eq!(tokens[3].token_type, TokenType::String);
+    assert_eq!(tokens[4].token_type, TokenType::SemiColon);
+  }
+
+  #[test]
+  fn test_number_exponents() {
+    let (tokens, engine) = scan("1e3 1.5e-2 1E+10 2.5E7;");
+
+    assert!(!engine.has_errors());
+    let values = tokens[..4]
+      .iter()
+      .map(|token| token.lexeme.parse::<f64>().unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(values, vec![1000.0, 0.015, 1e10, 2.5e7]);
+    assert_eq!(tokens[4].token_type, TokenType::SemiColon);
+  }
+
+  #[test]
+  fn test_leading_dot_number_exponents() {
+    let (tokens, engine) = scan(".5e3 .25E-2;");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::Number,
+        TokenType::Number,
+        TokenType::SemiColon,
+        TokenType::Eof
+      ]
+    );
+    assert_eq!(tokens[0].lexeme, "0.5e3");
+    assert_eq!(tokens[0].lexeme.parse::<f64>().unwrap(), 500.0);
+    assert_eq!(tokens[1].lexeme.parse::<f64>().unwrap(), 0.0025);
+
+    // A bad exponent is reported like it is after `0.5`.
+    let (_, engine) = scan(".5e;");
+    assert_eq!(engine.error_count(), 1);
+    assert_eq!(
+      engine.get_diagnostics()[0].code,
+      DiagnosticCode::InvalidNumber
+    );
+  }
+
+  #[test]
+  fn test_exponent_is_part_of_the_number() {
+    // Before exponents were scanned `1.5e10` split into `1.5` and the identifier `e10`.
+    let (tokens, engine) = scan("var x = 1.5e10 + 2E10;");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::Var,
+        TokenType::Identifier,
+        TokenType::Equal,
+        TokenType::Number,
+        TokenType::Plus,
+        TokenType::Number,
+        TokenType::SemiColon,
+        TokenType::Eof,
+      ]
+    );
+    assert_eq!(tokens[3].lexeme, "1.5e10");
+    assert_eq!(tokens[5].lexeme, "2E10");
+  }
+
+  #[test]
+  fn test_invalid_number_exponents() {
+    for source in ["1e;", "1e+;", "1e2.3;"] {
+      let (tokens, engine) = scan(source);
+
+      assert_eq!(engine.error_count(), 1, "{}", source);
+      assert_eq!(
+        engine.get_diagnostics()[0].code,
+        DiagnosticCode::InvalidNumber
+      );
+      // The bad literal is still a single token.
+      assert_eq!(
+        token_types(&tokens),
+        vec![TokenType::Number, TokenType::SemiColon, TokenType::Eof]
+      );
+    }
+  }
+
+  #[test]
+  fn test_scan_into_custom_sink() {
+    struct Lines(Vec<usize>);
+
+    impl ScannerErrorSink for Lines {
+      fn report_error(&mut self, diagnostic: Diagnostic) {
+        self.0.push(diagnostic.labels[0].span.line);
+      }
+    }
+
+    let mut lines = Lines(vec![]);
+    let mut scanner = Scanner::new("a;\n@\nb # c;".to_string());
+    scanner.scan(&mut lines);
+    assert_eq!(lines.0, vec![2, 3]);
+
+    let mut errors: Vec<Diagnostic> = vec![];
+    Scanner::new("@".to_string()).scan(&mut errors);
+    assert_eq!(errors[0].code, DiagnosticCode::InvalidCharacter);
+  }
+
+  #[test]
+  fn test_scan_tokens_without_engine() {
+    let (tokens, warnings) = Scanner::new("var a = 1;".to_string())
+      .scan_tokens()
+      .unwrap();
+    assert!(tokens.check(TokenType::Var));
+    assert!(warnings.is_empty());
+
+    let errors = Scanner::new("\"open".to_string())
+      .scan_tokens()
+      .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, DiagnosticCode::UnterminatedString);
+  }
+
+  #[test]
+  fn test_keywords_and_identifiers() {
+    let (tokens, _) = scan("while whiles super _nil nil");
+
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::While,
+        TokenType::Identifier,
+        TokenType::Super,
+        TokenType::Identifier,
+        TokenType::Nil,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_fixed_lexemes_are_borrowed() {
+    let (tokens, _) = scan("var x += 1;");
+
+    assert!(matches!(tokens[0].lexeme, Cow::Borrowed("var")));
+    assert!(matches!(tokens[1].lexeme, Cow::Owned(_)));
+    assert!(matches!(tokens[2].lexeme, Cow::Borrowed("+=")));
+    assert!(matches!(tokens[3].lexeme, Cow::Owned(_)));
+    assert!(matches!(tokens[4].lexeme, Cow::Borrowed(";")));
+  }
+
+  #[test]
+  fn test_token_validate() {
+    let (tokens, engine) = scan("var s = \"\"; s += 1; var t = `${s}${s}`;");
+
+    assert!(!engine.has_errors());
+    assert!(tokens.iter().all(|token| token.validate()));
+
+    let empty = Token::new(TokenType::Identifier, "", Literal::Nil, (0, 0), 0, 0);
+    assert!(!empty.validate());
+  }
+
+  #[test]
+  fn test_line_numbers_are_one_based() {
+    let (tokens, _) = scan("var a = 1; var b = 2;\n// note\n`x\ny`; c;");
+    let lines = tokens
+      .iter()
+      .map(|token| token.position.0)
+      .collect::<Vec<_>>();
+
+    // Both statements of the first line, then the string closing on line 4 and `c;`.
+    assert_eq!(lines, vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 4, 4, 4, 4, 4]);
+  }
+
+  #[test]
+  fn test_first_token_of_each_line_is_column_one() {
+    let (tokens, _) = scan("var a = 1;\nprint(a);\n/* note\n*/\nx;\n\n  y;");
+
+    for lexeme in ["var", "print", "x"] {
+      let token = tokens.iter().find(|token| token.lexeme == lexeme).unwrap();
+      assert_eq!(token.position.1, 1, "{}", lexeme);
+    }
+
+    // Columns count from the start of the token, indentation included.
+    let a = tokens.iter().find(|token| token.lexeme == "a").unwrap();
+    let y = tokens.iter().find(|token| token.lexeme == "y").unwrap();
+    assert_eq!(a.position, (1, 5));
+    assert_eq!(y.position, (7, 3));
+  }
+
+  #[test]
+  fn test_hexadecimal_literals() {
+    for (source, lexeme) in [("0xFF", "255"), ("0X1f", "31"), ("0x0", "0")] {
+      let (tokens, engine) = scan(source);
+
+      assert!(!engine.has_errors(), "{}", source);
+      assert_eq!(tokens[0].token_type, TokenType::Number);
+      assert_eq!(tokens[0].lexeme, lexeme);
+    }
+  }
+
+  #[test]
+  fn test_malformed_hexadecimal_literals() {
+    let (_, engine) = scan("0x;");
+    assert_eq!(engine.error_count(), 1);
+    assert_eq!(
+      engine.get_diagnostics()[0].code,
+      DiagnosticCode::InvalidNumber
+    );
+
+    // The scan stops at the first non-hex digit and points at it.
+    let (tokens, engine) = scan("0xFG");
+    assert_eq!(engine.error_count(), 1);
+    let diagnostic = &engine.get_diagnostics()[0];
+    assert_eq!(diagnostic.message, "Invalid digit 'G' in number literal");
+    assert_eq!(diagnostic.labels[0].span.column, 4);
+    assert_eq!(tokens[0].lexeme, "15");
+  }
+
+  #[test]
+  fn test_binary_and_octal_literals() {
+    for (source, lexeme) in [("0b1010", "10"), ("0B1", "1"), ("0o17", "15"), ("0O0", "0")] {
+      let (tokens, engine) = scan(source);
+
+      assert!(!engine.has_errors(), "{}", source);
+      assert_eq!(tokens[0].token_type, TokenType::Number);
+      assert_eq!(tokens[0].lexeme, lexeme);
+    }
+  }
+
+  #[test]
+  fn test_digit_outside_the_radix_is_reported() {
+    for (source, column) in [("0b2", 3), ("0b102", 5), ("0o8", 3)] {
+      let (_, engine) = scan(source);
+
+      assert_eq!(engine.error_count(), 1, "{}", source);
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::InvalidNumber);
+      assert_eq!(diagnostic.labels[0].span.column, column, "{}", source);
+    }
+  }
+
+  fn scan_with_comments(source: &str) -> Vec<Token> {
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new(source.to_string()).with_comments(true);
+    scanner.scan(&mut engine);
+
+    assert!(!engine.has_errors());
+    scanner.tokens
+  }
+
+  #[test]
+  fn test_doc_comments() {
+    let tokens = scan_with_comments("/// doc comment\n// regular\n//! module doc\n//// banner");
+
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::DocComment,
+        TokenType::Comment,
+        TokenType::DocComment,
+        TokenType::Comment,
+        TokenType::Eof
+      ]
+    );
+    assert_eq!(tokens[0].lexeme, "doc comment");
+    assert_eq!(tokens[2].lexeme, "module doc");
+
+    let tokens = scan_with_comments("///");
+    assert_eq!(tokens[0].lexeme, "");
+    assert!(tokens[0].validate());
+  }
+
+  #[test]
+  fn test_multi_line_doc_comment_block() {
+    let tokens = scan_with_comments("/// first\n/// second\nfun f() {}");
+
+    assert_eq!(tokens[0].lexeme, "first");
+    assert_eq!(
+      (tokens[1].lexeme.as_ref(), tokens[1].position.0),
+      ("second", 2)
+    );
+    assert_eq!(tokens[2].token_type, TokenType::Fun);
+  }
+
+  #[test]
+  fn test_comments_are_dropped_by_default() {
+    let (tokens, _) = scan("/// doc\n// plain\n/* block */ x");
+
+    assert_eq!(
+      token_types(&tokens),
+      vec![TokenType::Identifier, TokenType::Eof]
+    );
+  }
+
+  #[test]
+  fn test_stream_always_ends_with_an_empty_eof() {
+    for source in [
+      "",
+      "var a = 1;",
+      "// only a comment",
+      "\"unterminated",
+      "a\n\n",
+    ] {
+      let (tokens, _) = scan(source);
+
+      let last = tokens.last().unwrap();
+      assert!(last.is_eof(), "{:?}", source);
+      assert_eq!(last.lexeme, "", "{:?}", source);
+      assert_eq!(
+        (last.span_start, last.span_end),
+        (source.len(), source.len())
+      );
+      assert_eq!(tokens.iter().filter(|token| token.is_eof()).count(), 1);
+    }
+  }
+
+  #[test]
+  fn test_template_string_is_split_around_interpolations() {
+    let (tokens, engine) = scan("`a ${x} b ${ {y: 1} }${z}`;");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::TemplateHead,
+        TokenType::InterpolationStart,
+        TokenType::Identifier,
+        TokenType::InterpolationEnd,
+        TokenType::TemplateMiddle,
+        TokenType::InterpolationStart,
+        TokenType::LeftBrace,
+        TokenType::Identifier,
+        TokenType::Colon,
+        TokenType::Number,
+        TokenType::RightBrace,
+        TokenType::InterpolationEnd,
+        TokenType::TemplateMiddle,
+        TokenType::InterpolationStart,
+        TokenType::Identifier,
+        TokenType::InterpolationEnd,
+        TokenType::TemplateTail,
+        TokenType::SemiColon,
+        TokenType::Eof,
+      ]
+    );
+    assert_eq!(tokens[0].lexeme, "a ");
+    assert_eq!(tokens[0].literal, Literal::Template);
+    assert_eq!(tokens[4].lexeme, " b ");
+    assert_eq!(tokens[12].lexeme, "");
+    assert_eq!(tokens[16].lexeme, "");
+  }
+
+  #[test]
+  fn test_interpolation_left_open_at_eof() {
+    for source in ["`a ${b", "`a ${ {b}", "`a ${b} c ${d"] {
+      let (tokens, engine) = scan(source);
+
+      assert_eq!(engine.error_count(), 1, "expected one error for {}", source);
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::UnterminatedString);
+      assert_eq!(diagnostic.message, "unterminated template interpolation");
+      assert_eq!(tokens.last().unwrap().token_type, TokenType::Eof);
+    }
+
+    // The label points at the `${` that was never closed.
+    let (_, engine) = scan("`a ${b} c ${d");
+    let span = &engine.get_diagnostics()[0].labels[0].span;
+    assert_eq!(span.byte_offset, 10);
+  }
+
+  #[test]
+  fn test_escaped_dollar_in_template_string() {
+    let (tokens, engine) = scan(r"`costs \${price}`");
+
+    assert!(!engine.has_errors());
+    assert_eq!(tokens[0].token_type, TokenType::String);
+    assert_eq!(tokens[0].lexeme, "costs ${price}");
+
+    let (tokens, engine) = scan(r"`\${a} ${b}`");
+    assert!(!engine.has_errors());
+    assert_eq!(tokens[0].token_type, TokenType::TemplateHead);
+    assert_eq!(tokens[0].lexeme, "${a} ");
+
+    // Other strings have nothing to escape a `$` from.
+    let (_, engine) = scan(r#""\$""#);
+    assert_eq!(
+      engine.get_diagnostics()[0].code,
+      DiagnosticCode::InvalidEscapeSequence('$')
+    );
+  }
+
+  #[test]
+  fn test_backtick_string_without_interpolation_stays_a_string() {
+    let (tokens, engine) = scan("`costs $5 {}`");
+
+    assert!(!engine.has_errors());
+    assert_eq!(tokens[0].token_type, TokenType::String);
+    assert_eq!(tokens[0].lexeme, "costs $5 {}");
+  }
+
+  #[test]
+  fn test_form_feed_and_vertical_tab_are_whitespace() {
+    let (tokens, engine) = scan("var a = 1;\x0C\nvar b\x0C= 2;\x0Bvar c = 3;");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      tokens
+        .iter()
+        .filter(|token| token.token_type == TokenType::Var)
+        .count(),
+      3
+    );
+    // The vertical tab starts line 3.
+    assert_eq!(tokens[10].lexeme, "var");
+    assert_eq!(tokens[10].position, (3, 1));
+  }
+
+  #[test]
+  fn test_compound_assignment_operators() {
+    let (tokens, engine) = scan("a += 1 -= 2 *= 3 /= 4 %= 5 % 6");
+
+    assert!(!engine.has_errors());
+    let operators = token_types(&tokens)
+      .into_iter()
+      .filter(|token_type| !matches!(token_type, TokenType::Identifier | TokenType::Number))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      operators,
+      vec![
+        TokenType::PlusEqual,
+        TokenType::MinusEqual,
+        TokenType::MultiplyEqual,
+        TokenType::DivideEqual,
+        TokenType::ModulusEqual,
+        TokenType::Modulus,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_scan_tokens_hands_the_tokens_over() {
+    let mut scanner = Scanner::new("var a;".to_string());
+
+    let (first, _) = scanner.scan_tokens().unwrap();
+    assert!(first.check(TokenType::Var));
+    assert!(scanner.tokens.is_empty());
+
+    // The source is used up and EOF was already emitted, a second scan has nothing new.
+    let (second, _) = scanner.scan_tokens().unwrap();
+    assert!(!second.check(TokenType::Var));
+  }
+
+  #[test]
+  fn test_power_operator() {
+    let (tokens, engine) = scan("2 ** 3 * 4 *= 5 ***6");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::Number,
+        TokenType::Power,
+        TokenType::Number,
+        TokenType::Multiply,
+        TokenType::Number,
+        TokenType::MultiplyEqual,
+        TokenType::Number,
+        TokenType::Power,
+        TokenType::Multiply,
+        TokenType::Number,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_bitwise_operators_next_to_logical_ones() {
+    let (tokens, engine) = scan("a & b && c | d || e ^ ~f << 1 >> 2 <= 3 >= 4");
+
+    assert!(!engine.has_errors());
+    let operators = token_types(&tokens)
+      .into_iter()
+      .filter(|token_type| !matches!(token_type, TokenType::Identifier | TokenType::Number))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      operators,
+      vec![
+        TokenType::Ampersand,
+        TokenType::And,
+        TokenType::Pipe,
+        TokenType::Or,
+        TokenType::Caret,
+        TokenType::Tilde,
+        TokenType::ShiftLeft,
+        TokenType::ShiftRight,
+        TokenType::LessEqual,
+        TokenType::GreaterEqual,
+        TokenType::Eof,
+      ]
+    );
+
+    let (tokens, _) = scan("a &= 1; b |= 2; c ^= 3;");
+    assert_eq!(tokens[1].token_type, TokenType::AmpersandEqual);
+    assert_eq!(tokens[5].token_type, TokenType::PipeEqual);
+    assert_eq!(tokens[9].token_type, TokenType::CaretEqual);
+  }
+
+  #[test]
+  fn test_arrows() {
+    let (tokens, engine) = scan("(a) -> b => c >= d == e - > f");
+
+    assert!(!engine.has_errors());
+    let operators = token_types(&tokens)
+      .into_iter()
+      .filter(|token_type| {
+        !matches!(
+          token_type,
+          TokenType::Identifier | TokenType::LeftParen | TokenType::RightParen
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      operators,
+      vec![
+        TokenType::Arrow,
+        TokenType::FatArrow,
+        TokenType::GreaterEqual,
+        TokenType::EqualEqual,
+        TokenType::Minus,
+        TokenType::Greater,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_optional_chaining_and_null_coalescing() {
+    let (tokens, engine) = scan("a?.b ?? c ? d : e");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::Identifier,
+        TokenType::QuestionDot,
+        TokenType::Identifier,
+        TokenType::QuestionQuestion,
+        TokenType::Identifier,
+        TokenType::Question,
+        TokenType::Identifier,
+        TokenType::Colon,
+        TokenType::Identifier,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_question_before_a_float_literal_is_a_ternary() {
+    let (tokens, engine) = scan("a ?.5 : b?..5");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::Identifier,
+        TokenType::Question,
+        TokenType::Number,
+        TokenType::Colon,
+        TokenType::Identifier,
+        TokenType::QuestionDot,
+        TokenType::Number,
+        TokenType::Eof,
+      ]
+    );
+    assert_eq!(tokens[2].lexeme, "0.5");
+    assert_eq!(tokens[6].lexeme, "0.5");
+  }
+
+  #[test]
+  fn test_range_and_spread_dots() {
+    let (tokens, engine) = scan("1..5 ...rest a.b ..0.5 ....");
+
+    assert!(!engine.has_errors());
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::Number,
+        TokenType::DotDot,
+        TokenType::Number,
+        TokenType::DotDotDot,
+        TokenType::Identifier,
+        TokenType::Identifier,
+        TokenType::Dot,
+        TokenType::Identifier,
+        TokenType::DotDot,
+        TokenType::Number,
+        TokenType::DotDotDot,
+        TokenType::Dot,
+        TokenType::Eof,
+      ]
+    );
+    assert_eq!(tokens[0].lexeme, "1");
+    assert_eq!(tokens[9].lexeme, "0.5");
+  }
+
+  #[test]
+  fn test_token_display() {
+    let (tokens, _) = scan("a = 1;\n  b + \"hi\";");
+
+    assert_eq!(tokens[0].to_string(), "Token(Identifier, 'a', line 1:1)");
+    assert_eq!(tokens[5].to_string(), "Token(Plus, '+', line 2:5)");
+    assert_eq!(tokens[6].to_string(), "Token(String, 'hi', line 2:7)");
+    assert_eq!(TokenType::Plus.to_string(), "+");
+    assert_eq!(Literal::Number.to_string(), "Number");
+  }
+
+  #[test]
+  fn test_tokens_carry_byte_spans() {
+    let source = "var name = \"hi\";\n-1.5";
+    let (tokens, _) = scan(source);
+
+    let spans = tokens
+      .iter()
+      .map(|token| &source[token.span_start..token.span_end])
+      .collect::<Vec<_>>();
+    assert_eq!(
+      spans,
+      vec!["var", "name", "=", "\"hi\"", ";", "-", "1.5", ""]
+    );
+    assert_eq!(tokens[7].span_start, source.len());
+  }
+
+  #[test]
+  fn test_two_char_operators_consume_exactly_two_chars() {
+    let operators = [
+      ("-=", TokenType::MinusEqual),
+      ("--", TokenType::MinusMinus),
+      ("->", TokenType::Arrow),
+      ("+=", TokenType::PlusEqual),
+      ("++", TokenType::PlusPlus),
+      ("/=", TokenType::DivideEqual),
+      ("*=", TokenType::MultiplyEqual),
+      ("**", TokenType::Power),
+      ("%=", TokenType::ModulusEqual),
+      ("!=", TokenType::BangEqual),
+      ("==", TokenType::EqualEqual),
+      ("=>", TokenType::FatArrow),
+      (">=", TokenType::GreaterEqual),
+      (">>", TokenType::ShiftRight),
+      ("<=", TokenType::LessEqual),
+      ("<<", TokenType::ShiftLeft),
+      ("&&", TokenType::And),
+      ("&=", TokenType::AmpersandEqual),
+      ("||", TokenType::Or),
+      ("|=", TokenType::PipeEqual),
+      ("^=", TokenType::CaretEqual),
+      ("?.", TokenType::QuestionDot),
+      ("??", TokenType::QuestionQuestion),
+      ("..", TokenType::DotDot),
+    ];
+
+    for (operator, token_type) in operators {
+      // No spaces, so an operator that swallows a char too many eats into `right`.
+      let (tokens, engine) = scan(&format!("left{}right", operator));
+
+      assert!(!engine.has_errors(), "{}", operator);
+      assert_eq!(
+        token_types(&tokens),
+        vec![
+          TokenType::Identifier,
+          token_type,
+          TokenType::Identifier,
+          TokenType::Eof,
+        ],
+        "{}",
+        operator
+      );
+      assert_eq!(tokens[1].lexeme, operator);
+      assert_eq!(tokens[2].lexeme, "right", "{}", operator);
+      assert_eq!(tokens[2].position.1, 7, "{}", operator);
+    }
+  }
+
+  #[test]
+  fn test_columns_count_chars_not_bytes() {
+    let (tokens, engine) = scan("var café = \"🦆🦆\"; 名前 = café;");
+
+    assert!(!engine.has_errors());
+    let ident = tokens.iter().find(|token| token.lexeme == "名前").unwrap();
+    assert_eq!(ident.token_type, TokenType::Identifier);
+    assert_eq!(ident.position, (1, 18));
+    assert_eq!(ident.to_span().length, 2);
+    assert_eq!(tokens[3].lexeme, "🦆🦆");
+    assert_eq!(tokens.last().unwrap().position, (1, 28));
+  }
+
+  #[test]
+  fn test_invalid_character_after_multibyte_chars() {
+    let (_, engine) = scan("\"é\" @");
+
+    let diagnostic = &engine.get_diagnostics()[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::InvalidCharacter);
+    assert_eq!(diagnostic.labels[0].span.column, 5);
+  }
+
+  #[test]
+  fn test_error_after_multi_line_comment_is_at_the_right_position() {
+    let (tokens, engine) = scan("/* one\n two */ a\n  @");
+
+    let a = tokens.iter().find(|token| token.lexeme == "a").unwrap();
+    assert_eq!(a.position, (2, 9));
+    assert_eq!(engine.error_count(), 1);
+    let span = &engine.get_diagnostics()[0].labels[0].span;
+    assert_eq!((span.line, span.column), (3, 3));
+  }
+
+  #[test]
+  fn test_block_comment_edges() {
+    // Closing right at the end of the source is not unterminated.
+    let (tokens, engine) = scan("a; /* done */");
+    assert!(!engine.has_errors());
+    assert_eq!(tokens.len(), 3);
+
+    // The opening `*` can't double as the closing one.
+    let (tokens, engine) = scan("/*/ still a comment */ b");
+    assert!(!engine.has_errors());
+    assert_eq!(tokens[0].lexeme, "b");
+
+    let (_, engine) = scan("x\n/* open *");
+    assert_eq!(engine.error_count(), 1);
+    let diagnostic = &engine.get_diagnostics()[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::UnterminatedComment);
+    assert_eq!(diagnostic.message, "unterminated multi-line comment");
+    assert_eq!(
+      (
+        diagnostic.labels[0].span.line,
+        diagnostic.labels[0].span.column
+      ),
+      (2, 1)
+    );
+  }
+
+  #[test]
+  fn test_scan_error_skips_the_rest_of_the_word() {
+    let (tokens, engine) = scan("var a = @@@ 1; b = #x; print(@\"s\");");
+
+    assert_eq!(engine.error_count(), 3);
+    let lengths = engine
+      .get_diagnostics()
+      .iter()
+      .map(|diagnostic| diagnostic.labels[0].span.length)
+      .collect::<Vec<_>>();
+    assert_eq!(lengths, vec![3, 2, 1]);
+
+    // Scanning carries on right after each skipped word.
+    assert_eq!(
+      token_types(&tokens),
+      vec![
+        TokenType::Var,
+        TokenType::Identifier,
+        TokenType::Equal,
+        TokenType::Number,
+        TokenType::SemiColon,
+        TokenType::Identifier,
+        TokenType::Equal,
+        TokenType::SemiColon,
+        TokenType::Identifier,
+        TokenType::LeftParen,
+        TokenType::String,
+        TokenType::RightParen,
+        TokenType::SemiColon,
+        TokenType::Eof,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_tokens_iter_and_into_tokens() {
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new("var a = 1;".to_string());
+    scanner.scan(&mut engine);
+
+    let lexemes = scanner
+      .tokens_iter()
+      .map(|token| token.lexeme.as_ref())
+      .collect::<Vec<_>>();
+    assert_eq!(lexemes, vec!["var", "a", "=", "1", ";", ""]);
+
+    let tokens = scanner.into_tokens();
+    assert_eq!(tokens.len(), 6);
+    assert_eq!(tokens[5].token_type, TokenType::Eof);
+  }
+
+  #[test]
+  fn test_scanner_borrows_a_str_source() {
+    let source = String::from("print(1);");
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new(source.as_str());
+    scanner.scan(&mut engine);
+
+    assert!(matches!(scanner.source, Cow::Borrowed(_)));
+    assert_eq!(scanner.tokens.len(), 6);
+    assert!(matches!(Scanner::new(source.clone()).source, Cow::Owned(_)));
+  }
+
+  #[test]
+  fn test_newline_tokens_are_opt_in() {
+    let source = "a = 1\n/* x\ny */ b = `c\nd` // e\n";
+
+    let (tokens, _) = scan(source);
+    assert!(!token_types(&tokens).contains(&TokenType::Newline));
+
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new(source);
+    scanner.emit_newlines = true;
+    scanner.scan(&mut engine);
+
+    assert!(!engine.has_errors());
+    // Only the two line breaks outside the comment and the string are tokens.
+    let newlines = scanner
+      .tokens
+      .iter()
+      .filter(|token| token.token_type == TokenType::Newline)
+      .map(|token| token.position)
+      .collect::<Vec<_>>();
+    assert_eq!(newlines, vec![(1, 6), (4, 8)]);
+  }
+
+  #[test]
+  fn test_custom_keywords_supplement_and_override_the_built_in_ones() {
+    let keywords = HashMap::from([
+      ("let".to_string(), TokenType::Var),
+      ("yes".to_string(), TokenType::True),
+      ("fun".to_string(), TokenType::Identifier),
+    ]);
+
+    let mut engine = DiagnosticEngine::new();
+    let mut scanner = Scanner::new("let fun = yes; var x;").with_keywords(keywords);
+    scanner.scan(&mut engine);
+
+    assert_eq!(
+      token_types(&scanner.tokens),
+      vec![
+        TokenType::Var,
+        TokenType::Identifier,
+        TokenType::Equal,
+        TokenType::True,
+        TokenType::SemiColon,
+        TokenType::Var,
+        TokenType::Identifier,
+        TokenType::SemiColon,
+        TokenType::Eof,
+      ]
+    );
+    assert_eq!(scanner.tokens[0].lexeme, "let");
+    assert_eq!(scanner.tokens[3].literal, Literal::Boolean);
+  }
+
+  #[test]
+  fn test_braced_unicode_escape() {
+    let (tokens, engine) = scan(r#""\u{1F986} \u{41}\u{00e9}""#);
+
+    assert!(!engine.has_errors());
+    assert_eq!(tokens[0].lexeme, "🦆 Aé");
+  }
+
+  #[test]
+  fn test_unicode_escape_that_is_not_a_scalar_value() {
+    for (source, message) in [
+      (r#""\uD800""#, "Invalid unicode code point: U+D800."),
+      (r#""\u{110000}""#, "Invalid unicode code point: U+110000."),
+    ] {
+      let (_, engine) = scan(source);
+
+      assert_eq!(engine.error_count(), 1, "{}", source);
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(diagnostic.code, DiagnosticCode::InvalidLiteral);
+      assert_eq!(diagnostic.message, message);
+      assert_eq!(diagnostic.labels[0].span.column, 2);
+      assert_eq!(diagnostic.labels[0].span.length, source.len() - 2);
+    }
+  }
+
+  #[test]
+  fn test_malformed_braced_unicode_escape() {
+    for source in [r#""\u{}""#, r#""\u{41""#, r#""\u{1234567}""#] {
+      let (_, engine) = scan(source);
+
+      let diagnostic = &engine.get_diagnostics()[0];
+      assert_eq!(
+        diagnostic.code,
+        DiagnosticCode::InvalidEscapeSequence('u'),
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn test_error_spans_carry_byte_offsets() {
+    for (source, offset) in [(r#""é" @"#, 5), (r#""é\q""#, 3), ("/* é", 0)] {
+      let (_, engine) = scan(source);
+
+      let span = &engine.get_diagnostics()[0].labels[0].span;
+      assert_eq!(span.byte_offset, offset, "{}", source);
+    }
+  }
+
+  #[test]
+  fn test_nested_comment_start_is_a_warning() {
+    let (tokens, engine) = scan("/* a /* b */ c");
+
+    assert!(!engine.has_errors());
+    assert_eq!(engine.warning_count(), 1);
+    let diagnostic = &engine.get_diagnostics()[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::NestedComment);
+    assert_eq!(diagnostic.labels[0].span.column, 6);
+    assert_eq!(tokens[0].lexeme, "c");
+
+    // Warnings alone don't fail the sink-less scan, they come back with the tokens.
+    let (tokens, warnings) = Scanner::new("/* a /* b */ c").scan_tokens().unwrap();
+    assert!(tokens.check(TokenType::Identifier));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].code, DiagnosticCode::NestedComment);
+  }
 }
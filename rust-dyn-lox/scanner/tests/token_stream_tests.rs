@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::diagnostic_code::DiagnosticCode;
+  use scanner::{
+    token::{stream::TokenStream, types::TokenType},
+    Scanner,
+  };
+
+  fn stream(source: &str) -> TokenStream {
+    Scanner::new(source.to_string()).scan_tokens().unwrap().0
+  }
+
+  #[test]
+  fn test_peek_and_advance() {
+    let mut tokens = stream("a = 1;");
+
+    assert_eq!(tokens.peek().unwrap().token_type, TokenType::Identifier);
+    assert_eq!(tokens.peek_next().unwrap().token_type, TokenType::Equal);
+    assert!(tokens.previous().is_none());
+
+    assert_eq!(tokens.advance().unwrap().lexeme, "a");
+    assert_eq!(tokens.previous().unwrap().lexeme, "a");
+    assert!(tokens.check(TokenType::Equal));
+    assert!(!tokens.check(TokenType::Number));
+  }
+
+  #[test]
+  fn test_advance_stops_at_eof() {
+    let mut tokens = stream(";");
+
+    tokens.advance();
+    assert!(tokens.is_at_end());
+    assert_eq!(tokens.advance().unwrap().token_type, TokenType::Eof);
+    assert_eq!(tokens.advance().unwrap().token_type, TokenType::Eof);
+    assert!(tokens.is_at_end());
+  }
+
+  #[test]
+  fn test_expect() {
+    let mut tokens = stream("( 1");
+
+    assert_eq!(
+      tokens.expect(TokenType::LeftParen).unwrap().token_type,
+      TokenType::LeftParen
+    );
+
+    let error = tokens.expect(TokenType::RightParen).unwrap_err();
+    assert_eq!(error.code, DiagnosticCode::UnexpectedToken);
+    // A failed expect leaves the cursor where it was.
+    assert!(tokens.check(TokenType::Number));
+
+    tokens.advance();
+    let error = tokens.expect(TokenType::RightParen).unwrap_err();
+    assert_eq!(error.code, DiagnosticCode::UnexpectedEof);
+  }
+}
@@ -0,0 +1,450 @@
+use diagnostic::{
+  diagnostic::{Diagnostic, LabelStyle, Span},
+  diagnostic_code::Severity,
+  DiagnosticEngine,
+};
+use parser::{expr::Expr, stmt::Stmt, Parser};
+use scanner::{
+  token::{stream::TokenStream, types::Literal, Token},
+  Scanner,
+};
+use std::collections::{HashMap, HashSet};
+
+use crate::resolver::Resolver;
+
+/// Finding of the linter, or an error that stopped the program from being linted
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+  /// Rule that produced the lint, `None` for scan, parse and resolve errors
+  pub rule: Option<LintRule>,
+  pub severity: Severity,
+  pub message: String,
+  pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+  UnusedVariable,
+  ShadowedVariable,
+  AlwaysTruthyIf,
+  AlwaysFalseWhile,
+  UncalledFunction,
+  ImplicitNilReturn,
+}
+
+/// Rules the linter checks, every rule is enabled by default
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+  pub unused_variables: bool,
+  pub shadowed_variables: bool,
+  pub always_truthy_if: bool,
+  pub always_false_while: bool,
+  pub uncalled_functions: bool,
+  pub implicit_nil_return: bool,
+}
+
+impl Default for LintConfig {
+  fn default() -> Self {
+    Self {
+      unused_variables: true,
+      shadowed_variables: true,
+      always_truthy_if: true,
+      always_false_while: true,
+      uncalled_functions: true,
+      implicit_nil_return: true,
+    }
+  }
+}
+
+impl LintConfig {
+  pub fn is_enabled(&self, rule: LintRule) -> bool {
+    match rule {
+      LintRule::UnusedVariable => self.unused_variables,
+      LintRule::ShadowedVariable => self.shadowed_variables,
+      LintRule::AlwaysTruthyIf => self.always_truthy_if,
+      LintRule::AlwaysFalseWhile => self.always_false_while,
+      LintRule::UncalledFunction => self.uncalled_functions,
+      LintRule::ImplicitNilReturn => self.implicit_nil_return,
+    }
+  }
+}
+
+/// Function that scans, parses and resolves `source` then lints it. When one of those stages
+/// reports errors they are returned instead, since the program can't be linted.
+pub fn lint(source: &str, config: &LintConfig) -> Vec<Lint> {
+  let mut engine = DiagnosticEngine::new();
+
+  let mut scanner = Scanner::new(source);
+  scanner.scan(&mut engine);
+  if !engine.proceed() {
+    return errors_of(engine);
+  }
+
+  let mut parser = Parser::new(TokenStream::new(scanner.tokens));
+  parser.parse(&mut engine);
+  if !engine.proceed() {
+    return errors_of(engine);
+  }
+
+  let mut resolver = Resolver::new();
+  resolver.run(&parser.ast, &mut engine);
+  if !engine.proceed() {
+    return errors_of(engine);
+  }
+
+  let mut linter = Linter::new(config);
+  linter.run(&parser.ast);
+  linter.lints
+}
+
+/// Function that turns the errors of `engine` into lints, the resolver warnings overlap with the
+/// lint rules so they are left out.
+fn errors_of(engine: DiagnosticEngine) -> Vec<Lint> {
+  engine
+    .into_diagnostics()
+    .into_iter()
+    .filter(|diagnostic| diagnostic.severity == Severity::Error)
+    .map(|diagnostic| Lint {
+      rule: None,
+      severity: diagnostic.severity,
+      span: primary_span(&diagnostic),
+      message: diagnostic.message,
+    })
+    .collect()
+}
+
+fn primary_span(diagnostic: &Diagnostic) -> Span {
+  diagnostic
+    .labels
+    .iter()
+    .find(|label| label.style == LabelStyle::Primary)
+    .map(|label| label.span.clone())
+    .unwrap_or(Span {
+      file: String::new(),
+      line: 0,
+      column: 0,
+      length: 0,
+      byte_offset: 0,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BindingKind {
+  Variable,
+  Parameter,
+  Function,
+  Class,
+}
+
+#[derive(Debug)]
+struct Binding {
+  token: Token,
+  kind: BindingKind,
+  used: bool,
+}
+
+/// Walks the resolved AST and collects the lints of the enabled rules
+pub struct Linter<'config> {
+  config: &'config LintConfig,
+  /// The first scope holds the globals
+  scopes: Vec<HashMap<String, Binding>>,
+  /// Globals read before their declaration, a function body may call a function declared later
+  early_globals: HashSet<String>,
+  /// Rules out `return;` lints in initializers, their implicit return is `this`
+  functions: Vec<bool>,
+  lints: Vec<Lint>,
+}
+
+impl<'config> Linter<'config> {
+  pub fn new(config: &'config LintConfig) -> Self {
+    Self {
+      config,
+      scopes: vec![HashMap::new()],
+      early_globals: HashSet::new(),
+      functions: vec![],
+      lints: vec![],
+    }
+  }
+
+  /// Function that lints `ast` and returns the lints sorted by position.
+  pub fn run(&mut self, ast: &[Stmt]) -> &[Lint] {
+    for stmt in ast {
+      self.lint_stmt(stmt);
+    }
+    self.end_scope();
+
+    self
+      .lints
+      .sort_by_key(|lint| (lint.span.line, lint.span.column));
+    &self.lints
+  }
+
+  fn lint_stmt(&mut self, stmt: &Stmt) {
+    match stmt {
+      Stmt::Expr(expr) => self.lint_expr(expr),
+      Stmt::VarDecl(name, value) => {
+        if let Some(value) = value {
+          self.lint_expr(value);
+        }
+        self.declare(name, BindingKind::Variable);
+      },
+      Stmt::Block(stmts) => {
+        self.scopes.push(HashMap::new());
+        for stmt in stmts.iter() {
+          self.lint_stmt(stmt);
+        }
+        self.end_scope();
+      },
+      Stmt::If(condition, then_branch, else_branch) => {
+        if let Some(true) = constant_truthiness(condition) {
+          self.report(
+            LintRule::AlwaysTruthyIf,
+            "This condition is always true".to_string(),
+            condition,
+          );
+        }
+
+        self.lint_expr(condition);
+        self.lint_stmt(then_branch);
+        if let Some(else_branch) = else_branch {
+          self.lint_stmt(else_branch);
+        }
+      },
+      Stmt::While(condition, body) => {
+        if let Some(false) = constant_truthiness(condition) {
+          self.report(
+            LintRule::AlwaysFalseWhile,
+            "This condition is always false, the loop never runs".to_string(),
+            condition,
+          );
+        }
+
+        self.lint_expr(condition);
+        self.lint_stmt(body);
+      },
+      Stmt::Fun(name, params, body) => {
+        if let Expr::Identifier(name) = name {
+          self.declare(name, BindingKind::Function);
+        }
+        self.lint_function(params, body, false);
+      },
+      Stmt::Class(name, superclass, methods, static_methods) => {
+        if let Expr::Identifier(name) = name {
+          self.declare(name, BindingKind::Class);
+        }
+        if let Some(superclass) = superclass {
+          self.lint_expr(superclass);
+        }
+
+        // Methods are called through instances, so they aren't bindings the rules look at.
+        for method in methods.iter().chain(static_methods.iter()) {
+          if let Stmt::Fun(name, params, body) = method {
+            let is_initializer = matches!(name, Expr::Identifier(name) if name.lexeme == "init");
+            self.lint_function(params, body, is_initializer);
+          }
+        }
+      },
+      Stmt::Return(keyword, value) => match value {
+        Some(value) => self.lint_expr(value),
+        None if self.functions.last() == Some(&false) => self.push(
+          LintRule::ImplicitNilReturn,
+          "This return has no value, the function returns nil".to_string(),
+          keyword.to_span(),
+        ),
+        None => {},
+      },
+      Stmt::Break(_) | Stmt::Continue(_) => {},
+    }
+  }
+
+  fn lint_function(&mut self, params: &[Expr], body: &Stmt, is_initializer: bool) {
+    self.functions.push(is_initializer);
+    self.scopes.push(HashMap::new());
+
+    for param in params {
+      if let Expr::Identifier(param) = param {
+        self.declare(param, BindingKind::Parameter);
+      }
+    }
+
+    // The body block shares the scope of the parameters, like the resolver sees it.
+    match body {
+      Stmt::Block(stmts) => {
+        for stmt in stmts.iter() {
+          self.lint_stmt(stmt);
+        }
+      },
+      body => self.lint_stmt(body),
+    }
+
+    self.end_scope();
+    self.functions.pop();
+  }
+
+  fn lint_expr(&mut self, expr: &Expr) {
+    match expr {
+      Expr::Identifier(name) => self.use_name(name),
+      Expr::Literal(_) | Expr::This(_) | Expr::Super(_, _) => {},
+      Expr::Unary { rhs, .. } => self.lint_expr(rhs),
+      Expr::Binary { lhs, rhs, .. } | Expr::Logical { lhs, rhs, .. } => {
+        self.lint_expr(lhs);
+        self.lint_expr(rhs);
+      },
+      // Writing a variable isn't using it.
+      Expr::Assign { value, .. } => self.lint_expr(value),
+      Expr::Ternary {
+        condition,
+        then_branch,
+        else_branch,
+      } => {
+        self.lint_expr(condition);
+        self.lint_expr(then_branch);
+        self.lint_expr(else_branch);
+      },
+      Expr::Call {
+        callee, arguments, ..
+      } => {
+        self.lint_expr(callee);
+        for argument in arguments {
+          self.lint_expr(argument);
+        }
+      },
+      Expr::Grouping(expr) => self.lint_expr(expr),
+      Expr::Array { elements, .. } => {
+        for element in elements {
+          self.lint_expr(element);
+        }
+      },
+      Expr::Map { entries, .. } => {
+        for (key, value) in entries {
+          self.lint_expr(key);
+          self.lint_expr(value);
+        }
+      },
+      Expr::Get { object, .. } => self.lint_expr(object),
+      Expr::Set { object, value, .. } => {
+        self.lint_expr(object);
+        self.lint_expr(value);
+      },
+      Expr::Template { parts, .. } => {
+        for part in parts {
+          self.lint_expr(part);
+        }
+      },
+    }
+  }
+
+  fn declare(&mut self, name: &Token, kind: BindingKind) {
+    let enclosing = &self.scopes[..self.scopes.len() - 1];
+    if let Some(line) = enclosing
+      .iter()
+      .rev()
+      .find_map(|scope| scope.get(name.lexeme.as_ref()))
+      .map(|shadowed| shadowed.token.position.0)
+    {
+      let message = format!("'{}' shadows the declaration on line {}", name.lexeme, line);
+      self.push(LintRule::ShadowedVariable, message, name.to_span());
+    }
+
+    // There is always at least the global scope.
+    self.scopes.last_mut().unwrap().insert(
+      name.lexeme.to_string(),
+      Binding {
+        token: name.clone(),
+        kind,
+        used: false,
+      },
+    );
+  }
+
+  fn use_name(&mut self, name: &Token) {
+    for scope in self.scopes.iter_mut().rev() {
+      if let Some(binding) = scope.get_mut(name.lexeme.as_ref()) {
+        binding.used = true;
+        return;
+      }
+    }
+
+    self.early_globals.insert(name.lexeme.to_string());
+  }
+
+  fn end_scope(&mut self) {
+    let is_global = self.scopes.len() == 1;
+    let Some(scope) = self.scopes.pop() else {
+      return;
+    };
+
+    for (name, binding) in scope {
+      if binding.used || (is_global && self.early_globals.contains(&name)) {
+        continue;
+      }
+
+      let (rule, message) = match binding.kind {
+        BindingKind::Variable if !is_global => (
+          LintRule::UnusedVariable,
+          format!("Variable '{}' is never used", name),
+        ),
+        BindingKind::Function => (
+          LintRule::UncalledFunction,
+          format!("Function '{}' is never called", name),
+        ),
+        _ => continue,
+      };
+
+      self.push(rule, message, binding.token.to_span());
+    }
+  }
+
+  /// Function that reports a lint pointing at the start of `expr`.
+  fn report(&mut self, rule: LintRule, message: String, expr: &Expr) {
+    if let Some(token) = first_token(expr) {
+      self.push(rule, message, token.to_span());
+    }
+  }
+
+  fn push(&mut self, rule: LintRule, message: String, span: Span) {
+    if self.config.is_enabled(rule) {
+      self.lints.push(Lint {
+        rule: Some(rule),
+        severity: Severity::Warning,
+        message,
+        span,
+      });
+    }
+  }
+}
+
+/// Function that returns whether `expr` is a literal that is always truthy or always falsy, with
+/// the same truthiness rules as the interpreter. `None` when it depends on the program.
+fn constant_truthiness(expr: &Expr) -> Option<bool> {
+  match expr {
+    Expr::Grouping(expr) => constant_truthiness(expr),
+    // A `for` without a condition loops on a `true` placed at (0, 0).
+    Expr::Literal(token) if token.position == (0, 0) => None,
+    Expr::Literal(token) => match token.literal {
+      Literal::Boolean => Some(token.lexeme == "true"),
+      Literal::Nil => Some(false),
+      Literal::Number => token.lexeme.parse::<f64>().ok().map(|n| n != 0.0),
+      Literal::String | Literal::Template => Some(!token.lexeme.is_empty()),
+    },
+    _ => None,
+  }
+}
+
+/// Function that returns the token a lint about `expr` points at.
+fn first_token(expr: &Expr) -> Option<&Token> {
+  match expr {
+    Expr::Literal(token) | Expr::Identifier(token) | Expr::This(token) => Some(token),
+    Expr::Grouping(expr) => first_token(expr),
+    Expr::Unary { operator, .. } => Some(operator),
+    Expr::Binary { lhs, .. } | Expr::Logical { lhs, .. } => first_token(lhs),
+    Expr::Assign { name, .. } => Some(name),
+    Expr::Ternary { condition, .. } => first_token(condition),
+    Expr::Call { callee, .. } | Expr::Get { object: callee, .. } => first_token(callee),
+    Expr::Set { object, .. } => first_token(object),
+    Expr::Array { bracket, .. } => Some(bracket),
+    Expr::Map { brace, .. } => Some(brace),
+    Expr::Template { backtick, .. } => Some(backtick),
+    Expr::Super(keyword, _) => Some(keyword),
+  }
+}
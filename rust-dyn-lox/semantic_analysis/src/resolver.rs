@@ -9,7 +9,9 @@ use std::collections::HashMap;
 
 pub struct Resolver {
   scopes: Vec<HashMap<String, VariableState>>,
-  locals: HashMap<String, usize>,
+  /// Scope depth of every resolved local, keyed by the token so two uses of the same name at
+  /// different places resolve independently.
+  locals: HashMap<Token, usize>,
   current_class: ClassType,
   current_superclass: ClassType,
 }
@@ -30,6 +32,12 @@ struct VariableState {
   line: usize,
 }
 
+impl Default for Resolver {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl Resolver {
   pub fn new() -> Self {
     Self {
@@ -47,7 +55,7 @@ impl Resolver {
   /// Entry points
   pub fn resolve_statements(&mut self, stmts: &Vec<Stmt>, engine: &mut DiagnosticEngine) {
     for s in stmts {
-      self.resolve_stmt(&s, engine);
+      self.resolve_stmt(s, engine);
     }
   }
 
@@ -184,7 +192,7 @@ impl Resolver {
         }
 
         // 5. Resolve STATIC methods (without 'this' in scope)
-        let prev_class = self.current_class;
+        let _prev_class = self.current_class;
         self.current_class = ClassType::StaticMethod;
 
         for method in static_methods.iter() {
@@ -204,7 +212,7 @@ impl Resolver {
     match expr {
       Expr::Identifier(token) => {
         if let Some(scope) = self.scopes.last() {
-          if let Some(is_defined) = scope.get(&token.lexeme) {
+          if let Some(is_defined) = scope.get(token.lexeme.as_ref()) {
             if !is_defined.defined {
               eprintln!(
                 "Can't read local variable '{}' in its own initializer",
@@ -213,11 +221,11 @@ impl Resolver {
             }
           }
         }
-        self.resolve_local(&token.lexeme);
+        self.resolve_local(token);
       },
       Expr::Call {
         callee,
-        paren,
+        paren: _,
         arguments,
       } => {
         self.resolve_expr(callee, engine);
@@ -225,16 +233,41 @@ impl Resolver {
           self.resolve_expr(argument, engine);
         }
       },
-      Expr::Unary { operator, rhs } => {
+      Expr::Unary { operator: _, rhs } => {
         self.resolve_expr(rhs, engine);
       },
-      Expr::Binary { lhs, operator, rhs } => {
+      Expr::Binary {
+        lhs,
+        operator: _,
+        rhs,
+      }
+      | Expr::Logical {
+        lhs,
+        operator: _,
+        rhs,
+      } => {
         self.resolve_expr(lhs, engine);
         self.resolve_expr(rhs, engine);
       },
       Expr::Grouping(expr) => {
         self.resolve_expr(expr, engine);
       },
+      Expr::Array { elements, .. } => {
+        for element in elements {
+          self.resolve_expr(element, engine);
+        }
+      },
+      Expr::Map { entries, .. } => {
+        for (key, value) in entries {
+          self.resolve_expr(key, engine);
+          self.resolve_expr(value, engine);
+        }
+      },
+      Expr::Template { parts, .. } => {
+        for part in parts {
+          self.resolve_expr(part, engine);
+        }
+      },
       Expr::Ternary {
         condition,
         then_branch,
@@ -246,7 +279,7 @@ impl Resolver {
       },
       Expr::Assign { name, value } => {
         self.resolve_expr(value, engine);
-        self.resolve_local(&name.lexeme);
+        self.resolve_local(name);
       },
       Expr::Literal(_) => {},
 
@@ -295,10 +328,10 @@ impl Resolver {
           return;
         }
 
-        self.resolve_local(&keyword.lexeme);
+        self.resolve_local(keyword);
       },
 
-      Expr::Super(keyword, method_name) => {
+      Expr::Super(keyword, _method_name) => {
         // Check 1: Must be inside a class
         if self.current_class == ClassType::None {
           let diagnostic = Diagnostic::new(
@@ -344,7 +377,7 @@ impl Resolver {
 
         // Resolve 'super' keyword. This finds the environment where the superclass
         // reference is stored, and records the depth in `self.locals`.
-        self.resolve_local(&keyword.lexeme);
+        self.resolve_local(keyword);
       },
     }
   }
@@ -365,12 +398,12 @@ impl Resolver {
     self.end_scope(engine);
   }
 
-  fn resolve_local(&mut self, name: &str) {
+  fn resolve_local(&mut self, name: &Token) {
     // Iterate from INNERMOST (last) to OUTERMOST (first)
     for (i, scope) in self.scopes.iter_mut().rev().enumerate() {
-      if let Some(local) = scope.get_mut(name) {
+      if let Some(local) = scope.get_mut(name.lexeme.as_ref()) {
         local.used = true;
-        self.locals.insert(name.to_string(), i);
+        self.locals.insert(name.clone(), i);
         return;
       }
     }
@@ -392,9 +425,10 @@ impl Resolver {
           )
           .with_label(Label::primary(
             Span {
-              line: state.line + 1,
+              line: state.line,
               column: 0,
               length: 25,
+              byte_offset: 0,
               file: "".to_string(),
             },
             Some("never used".to_string()),
@@ -417,7 +451,7 @@ impl Resolver {
     let scope = self.scopes.last_mut().unwrap();
 
     // Check for duplicate declaration in same scope
-    if scope.contains_key(&name.lexeme) {
+    if scope.contains_key(name.lexeme.as_ref()) {
       let diagnostic = Diagnostic::new(
         DiagnosticCode::VariableAlreadyDeclared,
         format!(
@@ -439,7 +473,7 @@ impl Resolver {
 
     // Mark as declared but not yet defined
     scope.insert(
-      name.lexeme.clone(),
+      name.lexeme.to_string(),
       VariableState {
         defined: false,
         used: false,
@@ -455,12 +489,12 @@ impl Resolver {
       return; // global, we do not track in local scope
     }
     let scope = self.scopes.last_mut().unwrap();
-    if let Some(local) = scope.get_mut(&name.lexeme) {
+    if let Some(local) = scope.get_mut(name.lexeme.as_ref()) {
       local.defined = true;
     }
   }
 
-  pub fn get_locals(&self) -> &HashMap<String, usize> {
+  pub fn get_locals(&self) -> &HashMap<Token, usize> {
     &self.locals
   }
 }
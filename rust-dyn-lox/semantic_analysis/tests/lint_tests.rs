@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+  use diagnostic::diagnostic_code::Severity;
+  use semantic_analysis::lint::{lint, LintConfig, LintRule};
+
+  type Found = Vec<(Option<LintRule>, usize)>;
+
+  /// Returns the rule and line of every lint of `source`.
+  fn lints(source: &str, config: &LintConfig) -> Found {
+    lint(source, config)
+      .into_iter()
+      .map(|lint| (lint.rule, lint.span.line))
+      .collect()
+  }
+
+  /// Returns the lints of `source` once with every rule enabled and once without `rule`.
+  fn with_and_without(source: &str, disable: fn(&mut LintConfig)) -> (Found, Found) {
+    let mut config = LintConfig::default();
+    let enabled = lints(source, &config);
+    disable(&mut config);
+    (enabled, lints(source, &config))
+  }
+
+  #[test]
+  fn test_unused_variable() {
+    let (enabled, disabled) = with_and_without(
+      "{\n  var unused = 1;\n  var used = 2;\n  print(used);\n}",
+      |config| config.unused_variables = false,
+    );
+
+    assert_eq!(enabled, vec![(Some(LintRule::UnusedVariable), 2)]);
+    assert!(disabled.is_empty());
+  }
+
+  #[test]
+  fn test_shadowed_variable() {
+    let (enabled, disabled) = with_and_without(
+      "var name = 1;\n{\n  var name = 2;\n  print(name);\n}\nprint(name);",
+      |config| config.shadowed_variables = false,
+    );
+
+    assert_eq!(enabled, vec![(Some(LintRule::ShadowedVariable), 3)]);
+    assert!(disabled.is_empty());
+  }
+
+  #[test]
+  fn test_always_truthy_if() {
+    let (enabled, disabled) = with_and_without(
+      "if (true) print(1);\nif (\"text\") print(2);\nif (0) print(3);",
+      |config| config.always_truthy_if = false,
+    );
+
+    assert_eq!(
+      enabled,
+      vec![
+        (Some(LintRule::AlwaysTruthyIf), 1),
+        (Some(LintRule::AlwaysTruthyIf), 2),
+      ]
+    );
+    assert!(disabled.is_empty());
+  }
+
+  #[test]
+  fn test_always_false_while() {
+    let (enabled, disabled) = with_and_without(
+      "while (false) print(1);\nwhile (nil) print(2);\nfor (;;) break;",
+      |config| config.always_false_while = false,
+    );
+
+    assert_eq!(
+      enabled,
+      vec![
+        (Some(LintRule::AlwaysFalseWhile), 1),
+        (Some(LintRule::AlwaysFalseWhile), 2),
+      ]
+    );
+    assert!(disabled.is_empty());
+  }
+
+  #[test]
+  fn test_uncalled_function() {
+    let (enabled, disabled) = with_and_without(
+      "fun never() {}\nfun caller() { return later(); }\nfun later() { return 1; }\ncaller();",
+      |config| config.uncalled_functions = false,
+    );
+
+    assert_eq!(enabled, vec![(Some(LintRule::UncalledFunction), 1)]);
+    assert!(disabled.is_empty());
+  }
+
+  #[test]
+  fn test_implicit_nil_return() {
+    let (enabled, disabled) = with_and_without(
+      "fun stop(n) {\n  if (n) return;\n  return n;\n}\nclass A {\n  init() {\n    return;\n  }\n}\nstop(1);",
+      |config| config.implicit_nil_return = false,
+    );
+
+    assert_eq!(enabled, vec![(Some(LintRule::ImplicitNilReturn), 2)]);
+    assert!(disabled.is_empty());
+  }
+
+  #[test]
+  fn test_errors_are_returned_instead_of_lints() {
+    let lints = lint("var = 1;", &LintConfig::default());
+
+    assert!(!lints.is_empty());
+    assert!(lints
+      .iter()
+      .all(|lint| lint.rule.is_none() && lint.severity == Severity::Error));
+  }
+}